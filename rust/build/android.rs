@@ -0,0 +1,70 @@
+//! Android NDK cross-compilation support
+//!
+//! Unlike macOS/iOS, there's no toolchain wired up by Cargo/rustc alone: CMake needs to be
+//! pointed at the NDK's own toolchain file, and the Rust target triple has to be translated
+//! into the ABI/platform vocabulary that toolchain file expects.
+
+use std::{env, path::PathBuf};
+
+use cmake::Config as CMakeConfig;
+
+use super::BuildContext;
+
+/// Locate the Android NDK root from the environment. `ANDROID_NDK_HOME` is the modern,
+/// widely-recognized name; `ANDROID_NDK_ROOT` is an older alias some toolchains still set.
+pub fn find_ndk_root() -> Option<PathBuf> {
+    env::var("ANDROID_NDK_HOME")
+        .or_else(|_| env::var("ANDROID_NDK_ROOT"))
+        .ok()
+        .map(PathBuf::from)
+        .filter(|p| p.exists())
+}
+
+/// Map a Rust Android target triple to the `CMAKE_ANDROID_ARCH_ABI` value the NDK's CMake
+/// toolchain file expects.
+pub fn android_arch_abi(target: &str) -> &'static str {
+    if target.starts_with("aarch64") {
+        "arm64-v8a"
+    } else if target.starts_with("armv7") {
+        "armeabi-v7a"
+    } else if target.starts_with("x86_64") {
+        "x86_64"
+    } else if target.starts_with("i686") {
+        "x86"
+    } else {
+        panic!("Unsupported Android target triple: {}", target);
+    }
+}
+
+/// The minimum supported API level (`ANDROID_PLATFORM`), overridable via the
+/// `ANDROID_PLATFORM` env var (e.g. `android-24`). Defaults to API 24, the first level with
+/// full 64-bit NDK support.
+pub fn android_platform() -> String {
+    env::var("ANDROID_PLATFORM").unwrap_or_else(|_| "android-24".to_string())
+}
+
+/// Point `cmake_config` at the NDK's CMake toolchain file and set the ABI/platform CMake
+/// expects, for an Android target in `ctx`. Panics with a clear message if the NDK can't be
+/// found, since there is no way to build the C++ core for Android without it.
+pub fn configure_android_build(cmake_config: &mut CMakeConfig, ctx: &BuildContext) {
+    let ndk_root = find_ndk_root().unwrap_or_else(|| {
+        panic!(
+            "Building for Android target '{}' requires the Android NDK. \
+             Set ANDROID_NDK_HOME (or ANDROID_NDK_ROOT) to the NDK root directory.",
+            ctx.target
+        )
+    });
+
+    let toolchain_file = ndk_root.join("build/cmake/android.toolchain.cmake");
+    if !toolchain_file.exists() {
+        panic!(
+            "Android NDK toolchain file not found at {} (checked NDK root {})",
+            toolchain_file.display(),
+            ndk_root.display()
+        );
+    }
+
+    cmake_config.define("CMAKE_TOOLCHAIN_FILE", &toolchain_file);
+    cmake_config.define("CMAKE_ANDROID_ARCH_ABI", android_arch_abi(&ctx.target));
+    cmake_config.define("ANDROID_PLATFORM", android_platform());
+}
@@ -4,16 +4,178 @@ use std::{
     env,
     fs::{self, copy, create_dir_all},
     path::Path,
-    process::Command,
 };
 
 use super::BuildContext;
 
+/// A cheap, non-cryptographic content hash (FNV-1a) used only to detect whether the
+/// autocxx bridge's inputs changed since the last build - not for anything
+/// security-sensitive.
+fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Hash the bridge entry point together with the files directly inside each include
+/// directory. Subdirectories are not descended into, so a change to a header nested below
+/// an include directory (e.g. `include/xgrammar/xgrammar.h`) does not change the hash.
+fn hash_bridge_inputs(ctx: &BuildContext) -> u64 {
+    let mut combined = Vec::new();
+    if let Ok(src) = fs::read(Path::new("rust/src/lib.rs")) {
+        combined.extend_from_slice(&src);
+    }
+    for dir in [
+        &ctx.src_include_dir,
+        &ctx.xgrammar_include_dir,
+        &ctx.dlpack_include_dir,
+        &ctx.picojson_include_dir,
+    ] {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                if let Ok(contents) = fs::read(entry.path()) {
+                    combined.extend_from_slice(&contents);
+                }
+            }
+        }
+    }
+    fnv1a_hash(&combined)
+}
+
+/// Returns true if the autocxx bridge's inputs changed since the last successful build,
+/// i.e. regeneration is actually needed. As a side effect, records the current hash so
+/// the next invocation can compare against it.
+pub fn bridge_inputs_changed(ctx: &BuildContext) -> bool {
+    let stamp_path = ctx.out_dir.join(".xgrammar_rs_bridge_hash");
+    let current_hash = hash_bridge_inputs(ctx);
+    let previous_hash = fs::read_to_string(&stamp_path)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok());
+    let _ = fs::write(&stamp_path, current_hash.to_string());
+    previous_hash != Some(current_hash)
+}
+
+/// The C++ standard to build XGrammar against, selected via Cargo features the same way
+/// the `cxx` crate exposes `"c++14"`/`"c++17"`/`"c++20"`. This is the single source of
+/// truth used both for the `cc`/MSVC compile flags and the libclang parse args.
+fn cpp_std() -> &'static str {
+    if cfg!(feature = "cpp20") {
+        "c++20"
+    } else if cfg!(feature = "cpp14") {
+        "c++14"
+    } else {
+        "c++17"
+    }
+}
+
+/// Target-aware toolchain overrides for cross-compilation, analogous to rustbuild's
+/// per-target `cc`/`cxx`/`ar`/sysroot configuration. The built-in preset covers device
+/// (non-simulator) iOS; Android NDK targets get their `--target`/`--sysroot` parse args
+/// from a separate block in [`build_autocxx_bridge`]. `XGRAMMAR_CXX`/`XGRAMMAR_AR`/`XGRAMMAR_SYSROOT`/
+/// `XGRAMMAR_TARGET_FLAGS` let a user override or extend any target, including ones with
+/// no built-in preset.
+struct CrossToolchain {
+    sysroot: Option<String>,
+    extra_flags: Vec<String>,
+}
+
+fn cross_toolchain(ctx: &BuildContext) -> CrossToolchain {
+    let mut extra_flags: Vec<String> = Vec::new();
+    let mut sysroot = None;
+
+    if ctx.is_ios() && !ctx.target.contains("sim") && !ctx.target.contains("x86_64-apple-ios") {
+        // Device (non-simulator) iOS: point clang at the iphoneos SDK.
+        let arch = if ctx.is_aarch64() { "arm64" } else { "x86_64" };
+        let version = env::var("IPHONEOS_DEPLOYMENT_TARGET").unwrap_or_else(|_| "17.0".into());
+        extra_flags.push(format!("--target={}-apple-ios{}", arch, version));
+        if let Ok(sdkroot) = env::var("SDKROOT") {
+            sysroot = Some(sdkroot);
+        }
+    }
+
+    if let Ok(cxx) = env::var("XGRAMMAR_CXX") {
+        println!("cargo:rustc-env=CXX={}", cxx);
+    }
+    if let Ok(ar) = env::var("XGRAMMAR_AR") {
+        println!("cargo:rustc-env=AR={}", ar);
+    }
+    if let Ok(s) = env::var("XGRAMMAR_SYSROOT") {
+        sysroot = Some(s);
+    }
+    if let Ok(flags) = env::var("XGRAMMAR_TARGET_FLAGS") {
+        extra_flags.extend(flags.split_whitespace().map(str::to_string));
+    }
+
+    CrossToolchain { sysroot, extra_flags }
+}
+
+/// Directory holding the committed, pre-generated bindings (produced by
+/// `cargo xtask codegen`), alongside the hash stamp `xtask tidy` checks for freshness.
+fn committed_bindings_dir(ctx: &BuildContext) -> std::path::PathBuf {
+    ctx.manifest_dir.join("rust/src/generated")
+}
+
+/// Whether to prefer the committed bindings over running autocxx/libclang at build
+/// time. This is the default so ordinary builds and docs.rs need no C++ toolchain;
+/// the `regenerate-bindings` feature opts back into running autocxx live (e.g. while
+/// iterating on `rust/src/lib.rs` before re-running `cargo xtask codegen`).
+fn use_committed_bindings(ctx: &BuildContext) -> bool {
+    !cfg!(feature = "regenerate-bindings") && committed_bindings_dir(ctx).join("bindings.rs").exists()
+}
+
+/// Copy the freshly generated (and already formatted/stripped) autocxx bindings from
+/// `ctx.out_dir` into the committed `rust/src/generated/bindings.rs`, alongside a
+/// `.bindings_hash` stamp recording the bridge inputs' hash at generation time - the same
+/// hash [`committed_bindings_are_stale`] later compares against. Called by `cargo xtask
+/// codegen` after [`build_autocxx_bridge`], [`format_generated_bindings_optional`] and
+/// [`strip_autocxx_generated_doc_comments`] have produced the final generated file.
+pub fn commit_generated_bindings(ctx: &BuildContext) -> std::io::Result<()> {
+    let gen_rs = ctx.out_dir.join("autocxx-build-dir/rs/autocxx-ffi-default-gen.rs");
+    let dest_dir = committed_bindings_dir(ctx);
+    create_dir_all(&dest_dir)?;
+    fs::copy(&gen_rs, dest_dir.join("bindings.rs"))?;
+    fs::write(dest_dir.join(".bindings_hash"), hash_bridge_inputs(ctx).to_string())?;
+    Ok(())
+}
+
+/// Returns `true` if the committed bindings are stale relative to the current bridge
+/// inputs (same hash as [`bridge_inputs_changed`], but comparing against the stamp
+/// recorded by `cargo xtask codegen` rather than the last build's `OUT_DIR` stamp).
+/// Used by the `xtask tidy` check so CI fails when someone edits `rust/src/lib.rs` or a
+/// header without re-running codegen.
+pub fn committed_bindings_are_stale(ctx: &BuildContext) -> bool {
+    let stamp_path = committed_bindings_dir(ctx).join(".bindings_hash");
+    let current_hash = hash_bridge_inputs(ctx);
+    let committed_hash = fs::read_to_string(&stamp_path)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok());
+    committed_hash != Some(current_hash)
+}
+
 /// Build the autocxx bridge for Rust/C++ interop
 pub fn build_autocxx_bridge(ctx: &BuildContext) {
     println!("cargo:rerun-if-changed=rust/src/lib.rs");
+    println!("cargo:rerun-if-changed=rust/src/generated");
+
+    if use_committed_bindings(ctx) {
+        println!("cargo:warning=using committed bindings from rust/src/generated (set feature `regenerate-bindings` to run autocxx live)");
+        return;
+    }
+
+    if !bridge_inputs_changed(ctx) {
+        println!("cargo:warning=autocxx bridge inputs unchanged, skipping regeneration");
+        return;
+    }
 
-    let mut extra_clang_args = vec!["-std=c++17".to_string()];
+    // Tied to the same `cpp_std()` selection as the compile step below: if the parse and
+    // the compile disagree on the standard, bindgen can default to an older standard than
+    // what XGrammar's headers actually need and misparse `if constexpr`, `std::optional`,
+    // structured bindings, etc. Always use the clang-driver spelling (`-std=c++NN`) here,
+    // even on MSVC/clang-cl targets, since this flag feeds libclang's parse, not cl.exe.
+    let mut extra_clang_args = vec![format!("-std={}", cpp_std())];
 
     // Windows: explicitly set the target to avoid ARM NEON header issues
     if ctx.is_windows() {
@@ -24,6 +186,43 @@ pub fn build_autocxx_bridge(ctx: &BuildContext) {
         }
     }
 
+    // Android NDK: point clang at the NDK's prebuilt sysroot and the target triple's
+    // API-level-suffixed clang target so bindgen's libclang can parse the standard
+    // library and NDK platform headers.
+    if ctx.is_android() {
+        let ndk_home = env::var("ANDROID_NDK_HOME")
+            .or_else(|_| env::var("ANDROID_NDK_ROOT"))
+            .or_else(|_| env::var("NDK_HOME"))
+            .expect("ANDROID_NDK_HOME (or ANDROID_NDK_ROOT/NDK_HOME) must be set for Android builds");
+        let api_level = env::var("ANDROID_PLATFORM")
+            .ok()
+            .and_then(|v| v.trim_start_matches("android-").parse::<u32>().ok())
+            .unwrap_or(24);
+        let host_tag = if cfg!(target_os = "macos") {
+            "darwin-x86_64"
+        } else if cfg!(target_os = "windows") {
+            "windows-x86_64"
+        } else {
+            "linux-x86_64"
+        };
+        let sysroot = Path::new(&ndk_home)
+            .join("toolchains/llvm/prebuilt")
+            .join(host_tag)
+            .join("sysroot");
+        extra_clang_args.push(format!("--target={}{}", ctx.target, api_level));
+        extra_clang_args.push(format!("--sysroot={}", sysroot.display()));
+    }
+
+    // WebAssembly: emscripten ships its own sysroot/libclang target; point clang at the
+    // wasm32 triple so bindgen doesn't default to the host's native headers.
+    if ctx.is_wasm() {
+        extra_clang_args.push(format!("--target={}", ctx.target));
+        if let Ok(emsdk) = env::var("EMSDK") {
+            let sysroot = Path::new(&emsdk).join("upstream/emscripten/cache/sysroot");
+            extra_clang_args.push(format!("--sysroot={}", sysroot.display()));
+        }
+    }
+
     // iOS Simulator: set correct target triple and sysroot for C++ headers
     if ctx.target.contains("apple-ios-sim") || ctx.target.contains("x86_64-apple-ios") {
         let arch = if ctx.is_aarch64() { "arm64" } else { "x86_64" };
@@ -34,6 +233,14 @@ pub fn build_autocxx_bridge(ctx: &BuildContext) {
         }
     }
 
+    // Generic cross-compilation overrides/presets, applied after the target-specific
+    // blocks above so `XGRAMMAR_*` env vars can override any of their decisions.
+    let cross = cross_toolchain(ctx);
+    if let Some(sysroot) = &cross.sysroot {
+        extra_clang_args.push(format!("-isysroot{}", sysroot));
+    }
+    extra_clang_args.extend(cross.extra_flags.iter().cloned());
+
     let extra_clang_args_refs: Vec<&str> = extra_clang_args.iter().map(|s| s.as_str()).collect();
 
     let mut autocxx_builder = autocxx_build::Builder::new(
@@ -51,8 +258,8 @@ pub fn build_autocxx_bridge(ctx: &BuildContext) {
     .expect("autocxx build failed");
 
     autocxx_builder
-        .flag_if_supported("-std=c++17")
-        .flag_if_supported("/std:c++17")
+        .flag_if_supported(format!("-std={}", cpp_std()))
+        .flag_if_supported(format!("/std:{}", cpp_std()))
         .flag_if_supported("/EHsc")
         .include(&ctx.src_include_dir)
         .include(&ctx.xgrammar_include_dir)
@@ -61,6 +268,16 @@ pub fn build_autocxx_bridge(ctx: &BuildContext) {
         .include(&ctx.xgrammar_src_dir)
         .include(&ctx.manifest_dir);
 
+    if let Ok(cxx) = env::var("XGRAMMAR_CXX") {
+        autocxx_builder.compiler(cxx);
+    }
+    if let Ok(ar) = env::var("XGRAMMAR_AR") {
+        autocxx_builder.archiver(ar);
+    }
+    for flag in &cross.extra_flags {
+        autocxx_builder.flag_if_supported(flag);
+    }
+
     if ctx.is_msvc() {
         let use_static = env::var("XGRAMMAR_RS_STATIC_CRT")
             .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
@@ -71,6 +288,39 @@ pub fn build_autocxx_bridge(ctx: &BuildContext) {
     autocxx_builder.compile("xgrammar_rs_bridge");
 }
 
+/// Emit a `compile_commands.json` next to the crate root describing the flags used to
+/// compile the generated autocxx shim, so clang-based tooling (clangd, rust-analyzer's
+/// C++ companions, IDE plugins) can resolve the same headers the build used.
+pub fn emit_compile_commands_json(ctx: &BuildContext) {
+    let file = ctx.out_dir.join("autocxx-build-dir/rs/autocxx-ffi-default-gen.cc");
+    let command = format!(
+        "c++ -std={} -I{} -I{} -I{} -I{} -I{} -I{} -c {} -o {}.o",
+        cpp_std(),
+        ctx.src_include_dir.display(),
+        ctx.xgrammar_include_dir.display(),
+        ctx.dlpack_include_dir.display(),
+        ctx.picojson_include_dir.display(),
+        ctx.xgrammar_src_dir.display(),
+        ctx.manifest_dir.display(),
+        file.display(),
+        file.display(),
+    );
+    let entry = format!(
+        "[\n  {{\n    \"directory\": {:?},\n    \"command\": {:?},\n    \"file\": {:?}\n  }}\n]\n",
+        ctx.manifest_dir.display().to_string(),
+        command,
+        file.display().to_string(),
+    );
+    let dest = ctx.manifest_dir.join("compile_commands.json");
+    if let Err(err) = fs::write(&dest, entry) {
+        println!(
+            "cargo:warning=failed to write compile_commands.json to {}: {}",
+            dest.display(),
+            err
+        );
+    }
+}
+
 /// Copy headers needed for generated Rust code
 pub fn copy_headers_for_generated_rust_code(ctx: &BuildContext) {
     let rs_dir = ctx.out_dir.join("autocxx-build-dir/rs");
@@ -96,20 +346,25 @@ pub fn copy_headers_for_generated_rust_code(ctx: &BuildContext) {
     );
 }
 
-/// Optionally format the generated bindings with rustfmt
+/// Optionally format the generated bindings by parsing them with `syn` and re-emitting
+/// them with `prettyplease`. This avoids shelling out to `rustfmt` (which may not be on
+/// `PATH` in minimal toolchains) and formats directly on the `syn::File` AST we already
+/// need to parse for `strip_autocxx_generated_doc_comments`.
 pub fn format_generated_bindings_optional(out_dir: &Path) {
     let gen_rs = out_dir.join("autocxx-build-dir/rs/autocxx-ffi-default-gen.rs");
-    if gen_rs.exists() {
-        match Command::new("rustfmt").arg(&gen_rs).status() {
-            Ok(status) => {
-                if !status.success() {
-                    eprintln!("rustfmt returned non-zero status on {}", gen_rs.display());
-                }
-            }
-            Err(err) => {
-                eprintln!("rustfmt not executed: {}", err);
+    let Ok(contents) = fs::read_to_string(&gen_rs) else {
+        return;
+    };
+    match syn::parse_file(&contents) {
+        Ok(ast) => {
+            let formatted = prettyplease::unparse(&ast);
+            if let Err(err) = fs::write(&gen_rs, formatted) {
+                eprintln!("failed to write formatted bindings to {}: {}", gen_rs.display(), err);
             }
         }
+        Err(err) => {
+            eprintln!("failed to parse generated bindings as Rust source: {}", err);
+        }
     }
 }
 
@@ -120,6 +375,11 @@ pub fn strip_autocxx_generated_doc_comments(out_dir: &Path) {
     // rustdoc warnings (broken intra-doc links, invalid HTML tags). We strip all `#[doc = ...]`
     // attributes from generated bindings to keep public docs clean; Rust-side wrappers and
     // re-exports provide their own documentation.
+    //
+    // This operates on the parsed `syn::File` AST (rather than scanning lines for
+    // `#[doc = ...]` text) so it can't be fooled by a doc string that happens to contain
+    // that literal text, and it re-emits the file through `prettyplease` so the result
+    // stays formatted.
     let debug = env::var("XGRAMMAR_RS_DEBUG_DOCSTRIP").is_ok();
     let rs_dir = out_dir.join("autocxx-build-dir/rs");
     if debug {
@@ -155,34 +415,51 @@ pub fn strip_autocxx_generated_doc_comments(out_dir: &Path) {
             }
             continue;
         };
-        if debug {
-            let count = contents.matches("#[doc =").count();
-            println!(
-                "cargo:warning=docstrip: {} contains {} #[doc =] lines",
-                file_name, count
-            );
-        }
-        let mut changed = false;
-        let mut removed = 0usize;
-        let mut out = String::with_capacity(contents.len());
-        for line in contents.lines() {
-            let trimmed = line.trim_start();
-            if trimmed.starts_with("#[doc =") {
-                changed = true;
-                removed += 1;
-                continue;
+        let Ok(mut ast) = syn::parse_file(&contents) else {
+            if debug {
+                println!("cargo:warning=docstrip: failed to parse {}", file_name);
             }
-            out.push_str(line);
-            out.push('\n');
-        }
-        if changed {
+            continue;
+        };
+        let removed = strip_doc_attrs_from_items(&mut ast.items);
+        if removed > 0 {
             if debug {
                 println!(
-                    "cargo:warning=docstrip: {} removed {} doc lines",
+                    "cargo:warning=docstrip: {} removed {} doc attributes",
                     file_name, removed
                 );
             }
-            let _ = fs::write(&path, out);
+            let _ = fs::write(&path, prettyplease::unparse(&ast));
+        }
+    }
+}
+
+/// Remove `#[doc = ...]` attributes from every item in `items`, recursing into modules.
+/// Returns the number of attributes removed.
+fn strip_doc_attrs_from_items(items: &mut [syn::Item]) -> usize {
+    let mut removed = 0usize;
+    for item in items {
+        let attrs = match item {
+            syn::Item::Struct(s) => Some(&mut s.attrs),
+            syn::Item::Enum(e) => Some(&mut e.attrs),
+            syn::Item::Fn(f) => Some(&mut f.attrs),
+            syn::Item::Impl(i) => Some(&mut i.attrs),
+            syn::Item::Trait(t) => Some(&mut t.attrs),
+            syn::Item::Const(c) => Some(&mut c.attrs),
+            syn::Item::Type(t) => Some(&mut t.attrs),
+            syn::Item::Mod(m) => {
+                if let Some((_, sub_items)) = &mut m.content {
+                    removed += strip_doc_attrs_from_items(sub_items);
+                }
+                Some(&mut m.attrs)
+            }
+            _ => None,
+        };
+        if let Some(attrs) = attrs {
+            let before = attrs.len();
+            attrs.retain(|attr| !attr.path().is_ident("doc"));
+            removed += before - attrs.len();
         }
     }
+    removed
 }
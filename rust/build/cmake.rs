@@ -9,7 +9,7 @@ use std::{
 use cmake::Config as CMakeConfig;
 
 use super::common::find_xgrammar_lib_dir;
-use super::BuildContext;
+use super::{BuildContext, SimdLevel};
 
 /// Clear the CMake build directory if the source directory has changed
 fn maybe_clear_cmake_build_dir(build_dir: &Path, source_dir: &Path) {
@@ -53,6 +53,157 @@ fn get_msvc_runtime_library(ctx: &BuildContext) -> &'static str {
     }
 }
 
+/// Acquire a jobserver token for the duration of the CMake sub-build and configure the
+/// generator's parallelism, so concurrent `*-sys` builds don't oversubscribe cores.
+///
+/// An explicit `XGRAMMAR_RS_BUILD_JOBS` takes precedence and sets `CMAKE_BUILD_PARALLEL_LEVEL`
+/// directly. Otherwise, when Cargo's jobserver is reachable via `CARGO_MAKEFLAGS`, its
+/// `--jobserver-auth` is forwarded so Make/Ninja acquire tokens from the same pool as every
+/// other build script. Failing both, `CMAKE_BUILD_PARALLEL_LEVEL` is set from `NUM_JOBS`.
+struct JobserverGuard {
+    client: Option<jobserver::Client>,
+    acquired: Option<jobserver::Acquired>,
+}
+
+fn configure_build_parallelism(cmake_config: &mut CMakeConfig) -> JobserverGuard {
+    if let Ok(jobs) = env::var("XGRAMMAR_RS_BUILD_JOBS") {
+        cmake_config.define("CMAKE_BUILD_PARALLEL_LEVEL", &jobs);
+        return JobserverGuard { client: None, acquired: None };
+    }
+
+    if let Some(client) = unsafe { jobserver::Client::from_env() } {
+        let acquired = client.acquire().ok();
+        cmake_config.build_arg(format!("--jobserver-auth={}", client.to_env_string()));
+        return JobserverGuard { client: Some(client), acquired };
+    }
+
+    if let Ok(num_jobs) = env::var("NUM_JOBS") {
+        cmake_config.define("CMAKE_BUILD_PARALLEL_LEVEL", &num_jobs);
+    }
+    JobserverGuard { client: None, acquired: None }
+}
+
+/// Emit the `-march`/`-mavx2`/`-mfpu`/`/arch:` flags for [`BuildContext::simd_level`] to the
+/// C++ masking kernels, instead of compiling a lowest-common-denominator binary. Honors
+/// [`BuildContext::is_portable_scalar`] by simply emitting nothing, since `SimdLevel::PortableScalar`
+/// has no associated flags.
+fn apply_simd_flags(cmake_config: &mut CMakeConfig, ctx: &BuildContext) {
+    let level = ctx.simd_level();
+    if ctx.is_msvc() {
+        if let Some(arch_flag) = level.msvc_arch_flag() {
+            cmake_config.cxxflag(arch_flag);
+        }
+        return;
+    }
+    for flag in level.compiler_flags() {
+        cmake_config.cflag(flag);
+        cmake_config.cxxflag(flag);
+    }
+}
+
+/// Whether to build a universal (arm64 + x86_64) macOS static library, via explicit opt-in
+/// (`XGRAMMAR_RS_MACOS_UNIVERSAL=1`) or implicitly when `CARGO_CFG_TARGET_ARCH` isn't set,
+/// which is the shape of a standalone `lipo`-style packaging invocation rather than a normal
+/// `cargo build` for a single target.
+fn macos_universal_requested() -> bool {
+    super::common::is_truthy_env("XGRAMMAR_RS_MACOS_UNIVERSAL") || env::var("CARGO_CFG_TARGET_ARCH").is_err()
+}
+
+/// `CMAKE_OSX_DEPLOYMENT_TARGET` for a macOS build: honors an explicit
+/// `MACOSX_DEPLOYMENT_TARGET` override, otherwise defaults to a reasonable per-arch floor
+/// since Apple Silicon only ever shipped with macOS 11+, while Intel binaries can still
+/// reasonably target 10.13.
+fn macos_deployment_target(ctx: &BuildContext) -> String {
+    if let Ok(v) = env::var("MACOSX_DEPLOYMENT_TARGET") {
+        return v;
+    }
+    if macos_universal_requested() || ctx.is_aarch64() {
+        "11.0".to_string()
+    } else {
+        "10.13".to_string()
+    }
+}
+
+/// After building a universal static library, confirm both architecture slices actually made
+/// it into the `.a` via `lipo -archs`, so a silently x86_64-only (or arm64-only) binary never
+/// gets shipped labeled as universal.
+fn verify_universal_slices(lib_path: &Path) {
+    let output = std::process::Command::new("lipo")
+        .arg("-archs")
+        .arg(lib_path)
+        .output()
+        .unwrap_or_else(|e| panic!("Failed to run `lipo -archs {}`: {}", lib_path.display(), e));
+    if !output.status.success() {
+        panic!(
+            "`lipo -archs {}` failed: {}",
+            lib_path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    let archs = String::from_utf8_lossy(&output.stdout);
+    for required in ["arm64", "x86_64"] {
+        if !archs.split_whitespace().any(|a| a == required) {
+            panic!(
+                "Universal macOS build requested but {} is missing the '{}' slice (found: {})",
+                lib_path.display(),
+                required,
+                archs.trim()
+            );
+        }
+    }
+}
+
+/// Whether `ctx.target` names a 32-bit architecture, judged from the triple rather than
+/// pointer width since this runs in the (64-bit) build script itself, not the target.
+fn is_32_bit_target(target: &str) -> bool {
+    target.starts_with("i686") || target.starts_with("armv7") || target.starts_with("thumbv7")
+}
+
+/// Force position-independent code so the static `libxgrammar.a` can be linked into a
+/// 32-bit target or a Rust `cdylib` without relocation errors. Unconditional unless the
+/// escape hatch `XGRAMMAR_RS_NO_PIC=1` is set for users who deliberately want non-PIC
+/// (e.g. a kernel/embedded target where PIC isn't wanted).
+fn apply_pic_flags(cmake_config: &mut CMakeConfig, ctx: &BuildContext) {
+    if super::common::is_truthy_env("XGRAMMAR_RS_NO_PIC") {
+        return;
+    }
+    cmake_config.define("CMAKE_POSITION_INDEPENDENT_CODE", "ON");
+    if !ctx.is_msvc() && is_32_bit_target(&ctx.target) {
+        cmake_config.cflag("-fPIC");
+        cmake_config.cxxflag("-fPIC");
+    }
+}
+
+/// Whether `name --version` runs successfully, i.e. the tool is installed and on `PATH`.
+fn binary_on_path(name: &str) -> bool {
+    std::process::Command::new(name)
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Select the Ninja generator when available (explicit `XGRAMMAR_RS_GENERATOR=Ninja`, or
+/// `ninja` found on `PATH`), and wire up `sccache`/`ccache` as a compiler launcher when
+/// present, so CI and iterative local builds don't pay for a serial, uncached C++ rebuild
+/// every time. Falls back silently to CMake's default generator/compiler when neither tool
+/// is found, so existing builds are unaffected.
+fn apply_generator_and_compiler_cache(cmake_config: &mut CMakeConfig) {
+    let want_ninja = env::var("XGRAMMAR_RS_GENERATOR")
+        .map(|g| g.eq_ignore_ascii_case("ninja"))
+        .unwrap_or(false)
+        || binary_on_path("ninja");
+    if want_ninja {
+        cmake_config.generator("Ninja");
+    }
+
+    let launcher = ["sccache", "ccache"].into_iter().find(|name| binary_on_path(name));
+    if let Some(launcher) = launcher {
+        cmake_config.define("CMAKE_C_COMPILER_LAUNCHER", launcher);
+        cmake_config.define("CMAKE_CXX_COMPILER_LAUNCHER", launcher);
+    }
+}
+
 /// Build the XGrammar C++ library using CMake
 pub fn build_xgrammar_cmake(ctx: &BuildContext) -> PathBuf {
     let cmake_build_dir = ctx.out_dir.join("build");
@@ -96,12 +247,21 @@ pub fn build_xgrammar_cmake(ctx: &BuildContext) -> PathBuf {
         cmake_config.cxxflag("-fno-lto");
     }
 
+    apply_pic_flags(&mut cmake_config, ctx);
+    apply_simd_flags(&mut cmake_config, ctx);
+    apply_generator_and_compiler_cache(&mut cmake_config);
+
     cmake_config.profile(build_profile);
 
     // Platform-specific configuration
     if ctx.is_macos() {
-        let arch = if ctx.is_aarch64() { "arm64" } else { "x86_64" };
-        cmake_config.define("CMAKE_OSX_ARCHITECTURES", arch);
+        if macos_universal_requested() {
+            cmake_config.define("CMAKE_OSX_ARCHITECTURES", "arm64;x86_64");
+        } else {
+            let arch = if ctx.is_aarch64() { "arm64" } else { "x86_64" };
+            cmake_config.define("CMAKE_OSX_ARCHITECTURES", arch);
+        }
+        cmake_config.define("CMAKE_OSX_DEPLOYMENT_TARGET", macos_deployment_target(ctx));
     } else if ctx.is_ios() {
         let is_sim = ctx.target.contains("apple-ios-sim") || ctx.target.contains("x86_64-apple-ios");
         let arch = if ctx.is_aarch64() { "arm64" } else { "x86_64" };
@@ -111,8 +271,11 @@ pub fn build_xgrammar_cmake(ctx: &BuildContext) -> PathBuf {
         if let Ok(dep_target) = env::var("IPHONEOS_DEPLOYMENT_TARGET") {
             cmake_config.define("CMAKE_OSX_DEPLOYMENT_TARGET", dep_target);
         }
+    } else if ctx.is_android() {
+        super::android::configure_android_build(&mut cmake_config, ctx);
     }
 
+    let _jobserver_guard = configure_build_parallelism(&mut cmake_config);
     cmake_config.build_target("xgrammar").build()
 }
 
@@ -122,6 +285,11 @@ pub fn link_xgrammar_static(ctx: &BuildContext, destination_path: &Path) {
     let lib_search_dir = find_xgrammar_lib_dir(&cmake_build_dir)
         .or_else(|| find_xgrammar_lib_dir(destination_path))
         .unwrap_or_else(|| destination_path.join("lib"));
+
+    if ctx.is_macos() && macos_universal_requested() {
+        verify_universal_slices(&lib_search_dir.join("libxgrammar.a"));
+    }
+
     println!("cargo:rustc-link-search=native={}", lib_search_dir.display());
     println!("cargo:rustc-link-lib=static=xgrammar");
 }
@@ -132,6 +132,263 @@ pub fn find_xgrammar_lib_dir(root: &Path) -> Option<PathBuf> {
         .and_then(|entry| entry.path().parent().map(|p| p.to_path_buf()))
 }
 
+/// Whether a located `libxgrammar` should be linked statically or dynamically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    Static,
+    Dylib,
+}
+
+/// Like [`find_xgrammar_lib_dir`], but also recognizes shared libraries
+/// (`libxgrammar.so`/`.dylib`) so `system` builds can reuse a distro-packaged build.
+pub fn find_xgrammar_lib(root: &Path) -> Option<(PathBuf, LinkKind)> {
+    let static_candidates = ["libxgrammar.a", "xgrammar.lib"];
+    let dylib_candidates = ["libxgrammar.so", "libxgrammar.dylib", "xgrammar.dll"];
+
+    WalkDir::new(root)
+        .max_depth(6)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .find_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let kind = if static_candidates.iter().any(|c| name == *c) {
+                LinkKind::Static
+            } else if dylib_candidates.iter().any(|c| name == *c) {
+                LinkKind::Dylib
+            } else {
+                return None;
+            };
+            entry.path().parent().map(|p| (p.to_path_buf(), kind))
+        })
+}
+
+/// `XGRAMMAR_LIB_LOCATION` + `XGRAMMAR_INCLUDE_LOCATION`, if the user has pointed us at a
+/// pre-installed XGrammar to link against instead of building one.
+pub fn system_lib_location() -> Option<(PathBuf, PathBuf)> {
+    println!("cargo:rerun-if-env-changed=XGRAMMAR_LIB_LOCATION");
+    println!("cargo:rerun-if-env-changed=XGRAMMAR_INCLUDE_LOCATION");
+    let lib = env::var("XGRAMMAR_LIB_LOCATION").ok()?;
+    let include = env::var("XGRAMMAR_INCLUDE_LOCATION").ok()?;
+    Some((abs_path(lib), abs_path(include)))
+}
+
+/// Link against a system-installed XGrammar, erroring clearly if the library/header is missing.
+pub fn link_system_xgrammar(lib_dir: &Path) {
+    let (found_dir, kind) = find_xgrammar_lib(lib_dir).unwrap_or_else(|| {
+        panic!(
+            "XGRAMMAR_LIB_LOCATION={} does not contain a libxgrammar static or shared library",
+            lib_dir.display()
+        )
+    });
+    println!("cargo:rustc-link-search=native={}", found_dir.display());
+    match kind {
+        LinkKind::Static => println!("cargo:rustc-link-lib=static=xgrammar"),
+        LinkKind::Dylib => println!("cargo:rustc-link-lib=dylib=xgrammar"),
+    }
+}
+
+/// Probe `llvm-config` (and versioned variants) for its reported lib directory.
+fn llvm_config_libdir() -> Option<PathBuf> {
+    let names = [
+        "llvm-config",
+        "llvm-config-18",
+        "llvm-config-17",
+        "llvm-config-16",
+        "llvm-config-15",
+        "llvm-config-14",
+    ];
+    for name in names {
+        let Ok(output) = Command::new(name).arg("--libdir").output() else {
+            continue;
+        };
+        if !output.status.success() {
+            continue;
+        }
+        let dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !dir.is_empty() {
+            return Some(PathBuf::from(dir));
+        }
+    }
+    None
+}
+
+/// `$(brew --prefix llvm)/lib`, if Homebrew and an `llvm` formula are both present.
+fn homebrew_llvm_libdir() -> Option<PathBuf> {
+    let output = Command::new("brew").args(["--prefix", "llvm"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let prefix = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if prefix.is_empty() {
+        return None;
+    }
+    Some(PathBuf::from(prefix).join("lib"))
+}
+
+/// A directory "contains libclang" if it has a `libclang.so*` or `libclang.dylib` file.
+fn dir_has_libclang(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries.filter_map(Result::ok).any(|entry| {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        name == "libclang.dylib" || name.starts_with("libclang.so")
+    })
+}
+
+/// Locate libclang on Linux/macOS the way C-toolchain crates probe for compilers: try
+/// `llvm-config`, then common distro/Homebrew prefixes, verifying each candidate actually
+/// contains a `libclang` shared library before trusting it.
+fn find_libclang_unix() -> Option<PathBuf> {
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    candidates.extend(llvm_config_libdir());
+    candidates.extend(homebrew_llvm_libdir());
+
+    if let Ok(entries) = fs::read_dir("/usr/lib") {
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name();
+            if name.to_string_lossy().starts_with("llvm-") {
+                candidates.push(entry.path().join("lib"));
+            }
+        }
+    }
+    candidates.push(PathBuf::from("/usr/local/opt/llvm/lib"));
+    candidates.push(PathBuf::from("/usr/lib/x86_64-linux-gnu"));
+    candidates.push(PathBuf::from("/usr/lib/aarch64-linux-gnu"));
+
+    candidates.into_iter().find(|dir| dir_has_libclang(dir))
+}
+
+fn is_windows_msvc_target(target: &str) -> bool {
+    target.contains("windows") && target.contains("msvc")
+}
+
+/// Assemble `INCLUDE`/`LIB` and locate `libclang` for an `*-pc-windows-msvc` target from a
+/// non-Windows host (e.g. a Linux CI runner cross-compiling with an xwin-provided
+/// toolchain): there's no drive to probe or `vswhere.exe` to shell out to here, so instead
+/// read the same variables a cross toolchain setup step (xwin, `cargo-xwin`, a CI action)
+/// is expected to have exported. Returns `false`, with a diagnostic listing what's missing,
+/// if the required variables aren't present.
+fn configure_msvc_cross_from_env() -> bool {
+    let vc_tools_dir = env::var("VCToolsInstallDir").ok();
+    let sdk_dir = env::var("WindowsSdkDir").ok();
+    let sdk_version = env::var("WindowsSdkVersion").ok().map(|v| v.trim_end_matches('\\').to_string());
+
+    let (Some(vc_tools_dir), Some(sdk_dir), Some(sdk_version)) = (vc_tools_dir, sdk_dir, sdk_version) else {
+        let mut missing = Vec::new();
+        if env::var("VCToolsInstallDir").is_err() {
+            missing.push("VCToolsInstallDir");
+        }
+        if env::var("WindowsSdkDir").is_err() {
+            missing.push("WindowsSdkDir");
+        }
+        if env::var("WindowsSdkVersion").is_err() {
+            missing.push("WindowsSdkVersion");
+        }
+        println!(
+            "cargo:warning=xgrammar-rs: cross-compiling to a windows-msvc target from a non-Windows \
+             host requires {} to be set (e.g. by an xwin-based toolchain setup step); missing: {}",
+            "VCToolsInstallDir, WindowsSdkDir, and WindowsSdkVersion",
+            missing.join(", ")
+        );
+        return false;
+    };
+
+    let vc_tools_dir = PathBuf::from(vc_tools_dir.trim_end_matches('\\'));
+    let sdk_dir = PathBuf::from(sdk_dir.trim_end_matches('\\'));
+    let sdk_include = sdk_dir.join("Include").join(&sdk_version);
+
+    let include_paths = [
+        vc_tools_dir.join("include"),
+        sdk_include.join("ucrt"),
+        sdk_include.join("shared"),
+        sdk_include.join("um"),
+        sdk_include.join("winrt"),
+    ];
+    let include_str: String = include_paths
+        .iter()
+        .filter(|p| p.exists())
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(";");
+    if !include_str.is_empty() {
+        unsafe {
+            env::set_var("INCLUDE", &include_str);
+        }
+    }
+
+    let lib_paths = [
+        vc_tools_dir.join("lib").join("x64"),
+        sdk_dir.join("Lib").join(&sdk_version).join("ucrt").join("x64"),
+        sdk_dir.join("Lib").join(&sdk_version).join("um").join("x64"),
+    ];
+    let lib_str: String = lib_paths
+        .iter()
+        .filter(|p| p.exists())
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(";");
+    if !lib_str.is_empty() {
+        unsafe {
+            env::set_var("LIB", &lib_str);
+        }
+    }
+
+    if env::var("LIBCLANG_PATH").is_err() {
+        let libclang_dir = env::var("LLVM_HOME")
+            .ok()
+            .or_else(|| env::var("LLVM").ok())
+            .map(PathBuf::from)
+            .filter(|dir| dir_has_libclang(dir));
+        if let Some(dir) = libclang_dir {
+            unsafe {
+                env::set_var("LIBCLANG_PATH", &dir);
+            }
+            println!("cargo:rustc-env=LIBCLANG_PATH={}", dir.display());
+        }
+    }
+
+    true
+}
+
+/// Find libclang and export `LIBCLANG_PATH` for the autocxx/bindgen pass, unless it is
+/// already set. Delegates to the Windows `vswhere`-based search on Windows; on
+/// Linux/macOS building a native target, probes `llvm-config` and common install prefixes;
+/// on Linux/macOS cross-compiling to `*-pc-windows-msvc`, reads the toolchain location from
+/// the environment instead (see [`configure_msvc_cross_from_env`]).
+pub fn find_libclang(ctx: &BuildContext) {
+    if env::var("LIBCLANG_PATH").is_ok() {
+        return;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        super::windows::configure_libclang_early();
+        return;
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        if is_windows_msvc_target(&ctx.target) {
+            configure_msvc_cross_from_env();
+            return;
+        }
+
+        if let Some(dir) = find_libclang_unix() {
+            unsafe {
+                env::set_var("LIBCLANG_PATH", &dir);
+            }
+            println!("cargo:rustc-env=LIBCLANG_PATH={}", dir.display());
+        } else {
+            println!(
+                "cargo:warning=xgrammar-rs: could not locate libclang; set LIBCLANG_PATH manually if the autocxx build fails"
+            );
+        }
+    }
+}
+
 /// Collect the build context from environment variables
 pub fn collect_build_context() -> BuildContext {
     println!("cargo:rerun-if-env-changed=XGRAMMAR_SRC_DIR");
@@ -140,11 +397,38 @@ pub fn collect_build_context() -> BuildContext {
     println!("cargo:rerun-if-env-changed=XGRAMMAR_RS_OFFLINE");
     println!("cargo:rerun-if-env-changed=CARGO_NET_OFFLINE");
     println!("cargo:rerun-if-env-changed=CARGO_HOME");
+    println!("cargo:rerun-if-env-changed=XGRAMMAR_RS_GENERATOR");
+    println!("cargo:rerun-if-env-changed=XGRAMMAR_RS_NO_PIC");
+    println!("cargo:rerun-if-env-changed=XGRAMMAR_RS_MACOS_UNIVERSAL");
 
     let manifest_dir =
         abs_path(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
     let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set"));
 
+    let target = env::var("TARGET").unwrap_or_default();
+    let host = env::var("HOST").unwrap_or_default();
+    let profile = env::var("PROFILE").unwrap_or_else(|_| "release".into());
+
+    // `masking_only` builds (e.g. for wasm32-unknown-unknown or other embedded targets
+    // that can't link the CMake-built C++ backend) only need the pure-Rust token-masking
+    // subset of the API, so skip fetching/building XGrammar entirely.
+    if cfg!(feature = "masking_only") {
+        let ctx = BuildContext {
+            manifest_dir: manifest_dir.clone(),
+            xgrammar_src_dir: manifest_dir.clone(),
+            out_dir,
+            src_include_dir: manifest_dir.join("rust/src"),
+            xgrammar_include_dir: PathBuf::new(),
+            dlpack_include_dir: PathBuf::new(),
+            picojson_include_dir: PathBuf::new(),
+            target,
+            host,
+            profile,
+        };
+        record_simd_level(&ctx);
+        return ctx;
+    }
+
     let xgrammar_repo_dir = if let Ok(p) = env::var("XGRAMMAR_SRC_DIR") {
         abs_path(p)
     } else {
@@ -182,6 +466,7 @@ pub fn collect_build_context() -> BuildContext {
 
     let xgrammar_src_dir = super::submodules::prepare_xgrammar_source_tree(
         &xgrammar_repo_dir,
+        &manifest_dir,
         &out_dir,
         &submodules_toml,
     );
@@ -191,11 +476,7 @@ pub fn collect_build_context() -> BuildContext {
     let picojson_include_dir = xgrammar_src_dir.join("3rdparty/picojson");
     let src_include_dir = manifest_dir.join("rust/src");
 
-    let target = env::var("TARGET").unwrap_or_default();
-    let host = env::var("HOST").unwrap_or_default();
-    let profile = env::var("PROFILE").unwrap_or_else(|_| "release".into());
-
-    BuildContext {
+    let ctx = BuildContext {
         manifest_dir,
         xgrammar_src_dir,
         out_dir,
@@ -206,5 +487,16 @@ pub fn collect_build_context() -> BuildContext {
         target,
         host,
         profile,
-    }
+    };
+    record_simd_level(&ctx);
+    ctx
+}
+
+/// Surface the SIMD/ISA tier [`BuildContext::simd_level`] picked for this build both as a
+/// human-readable `cargo:warning` and as `cargo:rustc-env=XGRAMMAR_RS_SIMD_LEVEL=...`, so
+/// `rust/src/matcher/bitmask_simd.rs` can read it back with `option_env!` at compile time
+/// and bound its runtime ISA detection by what was actually compiled in.
+fn record_simd_level(ctx: &BuildContext) {
+    println!("cargo:rustc-env=XGRAMMAR_RS_SIMD_LEVEL={}", ctx.simd_level().env_value());
+    println!("cargo:warning=xgrammar-rs: selected SIMD level {:?} for target {}", ctx.simd_level(), ctx.target);
 }
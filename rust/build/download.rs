@@ -0,0 +1,90 @@
+//! Build-strategy selection: compile from source, download a prebuilt static
+//! lib, or link a system-installed XGrammar.
+
+use std::{
+    env,
+    fs::{self, create_dir_all, File},
+    io::copy as io_copy,
+    path::PathBuf,
+};
+
+use super::common::submodule_cache_dir;
+use super::BuildContext;
+
+/// Which build strategy to use for obtaining `libxgrammar`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildStrategy {
+    /// Compile XGrammar from source via CMake (default, current behavior).
+    Compile,
+    /// Download a prebuilt static-lib archive for the target triple.
+    Download,
+    /// Link against a pre-installed XGrammar (see `XGRAMMAR_LIB_LOCATION`).
+    System,
+}
+
+/// Read the requested build strategy from `XGRAMMAR_RS_STRATEGY`, defaulting to `Compile`.
+pub fn build_strategy() -> BuildStrategy {
+    println!("cargo:rerun-if-env-changed=XGRAMMAR_RS_STRATEGY");
+    match env::var("XGRAMMAR_RS_STRATEGY").as_deref() {
+        Ok("download") => BuildStrategy::Download,
+        Ok("system") => BuildStrategy::System,
+        Ok("compile") | Err(_) => BuildStrategy::Compile,
+        Ok(other) => {
+            println!(
+                "cargo:warning=Unknown XGRAMMAR_RS_STRATEGY '{other}', falling back to 'compile'"
+            );
+            BuildStrategy::Compile
+        },
+    }
+}
+
+fn release_base() -> String {
+    env::var("XGRAMMAR_RS_RELEASE_BASE").unwrap_or_else(|_| {
+        "https://github.com/mlc-ai/xgrammar/releases/download".to_string()
+    })
+}
+
+/// Best-effort download of a prebuilt static-lib archive for `ctx.target`.
+///
+/// Returns the directory the archive was extracted into (the `libxgrammar` static lib
+/// lies somewhere beneath it), or `None`
+/// if no asset exists for this target (callers should fall back to `Compile`).
+pub fn download_prebuilt_lib(ctx: &BuildContext) -> Option<PathBuf> {
+    let version = env!("CARGO_PKG_VERSION");
+    let cache_dir = submodule_cache_dir(&ctx.out_dir);
+    let asset_name = format!("libxgrammar-{version}-{}.tar.gz", ctx.target);
+    let extract_dir = cache_dir.join(format!("prebuilt-{version}-{}", ctx.target));
+    let marker = extract_dir.join(".fetched");
+    if marker.exists() {
+        return Some(extract_dir);
+    }
+
+    let url = format!("{}/v{version}/{asset_name}", release_base());
+    println!("cargo:warning=xgrammar-rs: fetching prebuilt library from {url}");
+
+    create_dir_all(&cache_dir).ok()?;
+    let archive_path = cache_dir.join(&asset_name);
+    let response = ureq::get(&url).call().ok()?;
+    if response.status() != 200 {
+        println!(
+            "cargo:warning=xgrammar-rs: no prebuilt asset for target {} (HTTP {}), falling back to compile",
+            ctx.target,
+            response.status()
+        );
+        return None;
+    }
+    let mut file = File::create(&archive_path).ok()?;
+    io_copy(&mut response.into_reader(), &mut file).ok()?;
+    drop(file);
+
+    if extract_dir.exists() {
+        let _ = fs::remove_dir_all(&extract_dir);
+    }
+    create_dir_all(&extract_dir).ok()?;
+    let tar_gz = File::open(&archive_path).ok()?;
+    let tar = flate2::read::GzDecoder::new(tar_gz);
+    tar::Archive::new(tar).unpack(&extract_dir).ok()?;
+
+    let _ = fs::write(&marker, version);
+    Some(extract_dir)
+}
@@ -1,16 +1,71 @@
 //! macOS and iOS specific build configuration
 
+use std::{env, path::PathBuf, process::Command};
+
 use super::BuildContext;
 
-/// Configure macOS/iOS specific build settings
+/// Standard locations Homebrew installs its `llvm` formula to, tried when `brew --prefix
+/// llvm` itself isn't available (e.g. `brew` not on `PATH` inside some CI containers).
+const HOMEBREW_LLVM_PREFIXES: &[&str] = &["/opt/homebrew/opt/llvm", "/usr/local/opt/llvm"];
+
+/// `$(brew --prefix llvm)`, if Homebrew and an `llvm` formula are both present.
+fn homebrew_llvm_prefix() -> Option<PathBuf> {
+    let output = Command::new("brew").args(["--prefix", "llvm"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let prefix = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!prefix.is_empty()).then(|| PathBuf::from(prefix))
+}
+
+/// Find a Homebrew-installed `libclang.dylib`, which is usually newer and more complete than
+/// the one bundled with the Xcode Command Line Tools (which only ships a subset needed by
+/// the system clang driver, not the full libclang C API bindgen/autocxx need).
+fn find_homebrew_libclang() -> Option<PathBuf> {
+    let mut prefixes: Vec<PathBuf> = homebrew_llvm_prefix().into_iter().collect();
+    prefixes.extend(HOMEBREW_LLVM_PREFIXES.iter().map(PathBuf::from));
+
+    for prefix in prefixes {
+        let lib_dir = prefix.join("lib");
+        if lib_dir.join("libclang.dylib").exists() {
+            return Some(lib_dir);
+        }
+    }
+    None
+}
+
+/// Configure macOS/iOS specific build settings: when a Homebrew LLVM install provides a
+/// more complete `libclang.dylib` than the Xcode Command Line Tools, point the
+/// autocxx/bindgen pass at it (unless the user already set `LIBCLANG_PATH` themselves).
 pub fn configure_macos_build(_ctx: &BuildContext) {
-    // macOS typically has Xcode command line tools installed
-    // which provides clang/libclang automatically.
-    //
-    // If needed, we could add detection for:
-    // - Xcode installation path
-    // - Command Line Tools path
-    // - Homebrew LLVM installation
+    if env::var("LIBCLANG_PATH").is_ok() {
+        return;
+    }
+    if let Some(lib_dir) = find_homebrew_libclang() {
+        unsafe {
+            env::set_var("LIBCLANG_PATH", &lib_dir);
+        }
+        println!("cargo:rustc-env=LIBCLANG_PATH={}", lib_dir.display());
+        println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    }
+}
+
+/// Get the SDK path for the host macOS SDK, mirroring [`get_ios_sdk_path`]. Useful as an
+/// `-isysroot` hint for header discovery on systems that only have the Command Line Tools
+/// installed (no full Xcode.app), where headers aren't always found automatically.
+pub fn get_macos_sdk_path() -> Option<String> {
+    let output = Command::new("xcrun")
+        .args(["--sdk", "macosx", "--show-sdk-path"])
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout)
+            .ok()
+            .map(|s| s.trim().to_string())
+    } else {
+        None
+    }
 }
 
 /// Get the SDK path for iOS builds
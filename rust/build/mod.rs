@@ -5,9 +5,11 @@
 // Allow unused items as they're available for future use or platform-specific code
 #![allow(dead_code)]
 
+pub mod android;
 pub mod autocxx;
 pub mod cmake;
 pub mod common;
+pub mod download;
 pub mod submodules;
 
 #[cfg(target_os = "windows")]
@@ -64,6 +66,16 @@ impl BuildContext {
         self.target.contains("linux")
     }
 
+    /// Returns true if building for Android (an NDK cross-compile target)
+    pub fn is_android(&self) -> bool {
+        self.target.contains("android")
+    }
+
+    /// Returns true if building for a WebAssembly target (e.g. wasm32-unknown-emscripten)
+    pub fn is_wasm(&self) -> bool {
+        self.target.starts_with("wasm32") || self.target.starts_with("wasm64")
+    }
+
     /// Returns true if building for ARM64/AArch64
     pub fn is_aarch64(&self) -> bool {
         self.target.contains("aarch64")
@@ -92,8 +104,97 @@ impl BuildContext {
             "x86_64"
         } else if self.target.contains("i686") {
             "i686"
+        } else if self.target.starts_with("wasm32") {
+            "wasm32"
+        } else if self.target.starts_with("wasm64") {
+            "wasm64"
         } else {
             "unknown"
         }
     }
+
+    /// Whether this build should skip the native C++ xgrammar library entirely and only
+    /// compile the pure-Rust, `no_std`-friendly token-masking subset of the API (see
+    /// `rust/src/matcher/bitmask_simd.rs`). Selected by the `masking_only` cargo feature,
+    /// which is the only supported way to target `wasm32-unknown-unknown` or other
+    /// embedded environments that can't link the CMake-built C++ backend.
+    pub fn is_masking_only(&self) -> bool {
+        cfg!(feature = "masking_only")
+    }
+
+    /// Whether the vectorized masking kernels are force-disabled in favor of a portable
+    /// scalar build, via `XGRAMMAR_RS_PORTABLE_SCALAR=1` or the `portable_scalar` cargo
+    /// feature. Used for toolchains/targets where the vectorized path fails to compile,
+    /// or where reproducible bit-identical output across machines is required.
+    pub fn is_portable_scalar(&self) -> bool {
+        cfg!(feature = "portable_scalar") || common::is_truthy_env("XGRAMMAR_RS_PORTABLE_SCALAR")
+    }
+
+    /// Pick the SIMD/ISA tier to compile the C++ masking kernels for, bounding what the
+    /// runtime dispatch in `rust/src/matcher/bitmask_simd.rs` is allowed to select (see
+    /// [`SimdLevel::env_value`]). [`BuildContext::is_portable_scalar`] always wins; beyond
+    /// that this picks the widest tier the target ISA (not the host CPU, since cross
+    /// compilation is supported) guarantees at the ABI level: AVX2 on x86_64, NEON on
+    /// aarch64. AVX-512 is never auto-selected since it isn't a safe baseline assumption
+    /// for "x86_64" in general; opt in with `XGRAMMAR_RS_AVX512=1`.
+    pub fn simd_level(&self) -> SimdLevel {
+        if self.is_portable_scalar() {
+            SimdLevel::PortableScalar
+        } else if self.is_x86_64() {
+            if common::is_truthy_env("XGRAMMAR_RS_AVX512") {
+                SimdLevel::Avx512
+            } else {
+                SimdLevel::Avx2
+            }
+        } else if self.is_aarch64() {
+            SimdLevel::Neon
+        } else {
+            SimdLevel::PortableScalar
+        }
+    }
+}
+
+/// SIMD/ISA tier selected for a build, shared between the cmake/autocxx flag emission for
+/// the C++ masking kernels and the compile-time bound recorded for the pure-Rust runtime
+/// dispatch in `rust/src/matcher/bitmask_simd.rs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimdLevel {
+    Avx512,
+    Avx2,
+    Neon,
+    /// Lowest-common-denominator scalar code: no wide-SIMD instructions assumed.
+    PortableScalar,
+}
+
+impl SimdLevel {
+    /// Non-MSVC compiler flags (`cc`/cmake `cflag`/`cxxflag`) that enable this tier.
+    pub fn compiler_flags(self) -> &'static [&'static str] {
+        match self {
+            SimdLevel::Avx512 => &["-mavx512f", "-mavx512bw", "-mavx2", "-mfma"],
+            SimdLevel::Avx2 => &["-mavx2", "-mfma"],
+            SimdLevel::Neon => &["-mfpu=neon"],
+            SimdLevel::PortableScalar => &[],
+        }
+    }
+
+    /// MSVC `/arch:` flag, if this tier needs one beyond the default `cl.exe` baseline.
+    pub fn msvc_arch_flag(self) -> Option<&'static str> {
+        match self {
+            SimdLevel::Avx512 => Some("/arch:AVX512"),
+            SimdLevel::Avx2 => Some("/arch:AVX2"),
+            SimdLevel::Neon | SimdLevel::PortableScalar => None,
+        }
+    }
+
+    /// Stable string recorded via `cargo:rustc-env=XGRAMMAR_RS_SIMD_LEVEL=...` so
+    /// `rust/src/matcher/bitmask_simd.rs` can read it back with `option_env!` and bound its
+    /// runtime ISA detection by what this build actually compiled in.
+    pub fn env_value(self) -> &'static str {
+        match self {
+            SimdLevel::Avx512 => "avx512",
+            SimdLevel::Avx2 => "avx2",
+            SimdLevel::Neon => "neon",
+            SimdLevel::PortableScalar => "portable_scalar",
+        }
+    }
 }
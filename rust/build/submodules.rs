@@ -1,17 +1,336 @@
 //! Git submodule handling for fetching dependencies
 
 use std::{
-    fs::{self, create_dir_all},
+    fs::{self, create_dir_all, File},
+    io::{Read as _, Write as _},
     path::{Path, PathBuf},
     process::Command,
 };
 
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
 use super::common::{
-    cargo_offline, copy_dir_recursive_filtered, run_checked, submodule_cache_dir,
+    cargo_offline, copy_dir_recursive_filtered, is_truthy_env, run_checked, submodule_cache_dir,
 };
 
-/// Read pinned submodule information from submodules.toml
-pub fn read_pinned_submodule(submodules_toml: &Path, name: &str) -> (String, String) {
+/// Pinned submodule info: a git url+rev, optionally paired with a tarball mirror
+/// (`archive_url` + `sha256`) that lets the fetch skip the git dependency entirely.
+pub struct PinnedSubmodule {
+    pub url: String,
+    pub rev: String,
+    /// Destination path under the work tree, e.g. `3rdparty/dlpack`. Defaults to
+    /// `3rdparty/<name>` when the pin doesn't override it with a `path` key.
+    pub path: String,
+    /// A file expected to exist under `path` once materialized, used to validate the
+    /// fetch instead of hardcoding a single submodule's header. Defaults to `path`
+    /// itself (i.e. just checking the destination directory exists).
+    pub sentinel: Option<String>,
+    pub archive_url: Option<String>,
+    pub sha256: Option<String>,
+    /// Expected digest of the materialized git checkout tree (see
+    /// [`compute_tree_digest`]), used to catch a tampered pin or a cache directory that
+    /// was quietly rewritten to something other than what was fetched. Independent of
+    /// `sha256`, which only covers the `archive_url` tarball.
+    pub tree_sha256: Option<String>,
+}
+
+/// Every `[submodules.*]` section name declared in `submodules.toml`.
+pub fn list_submodule_names(submodules_toml: &Path) -> Vec<String> {
+    let Ok(contents) = fs::read_to_string(submodules_toml) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            let header = line.strip_prefix('[')?.strip_suffix(']')?;
+            header.strip_prefix("submodules.").map(str::to_string)
+        })
+        .collect()
+}
+
+/// Read pinned submodule information from submodules.toml, including the optional
+/// `path`/`sentinel` destination keys and `archive_url`/`sha256` tarball-mirror keys.
+pub fn read_pinned_submodule_full(submodules_toml: &Path, name: &str) -> PinnedSubmodule {
+    let contents = fs::read_to_string(submodules_toml).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read submodule pins at {}: {}.\n\
+             Run `bash scripts/update_rust_submodules.sh` to regenerate it.",
+            submodules_toml.display(),
+            e
+        )
+    });
+
+    let mut in_section = false;
+    let mut url: Option<String> = None;
+    let mut rev: Option<String> = None;
+    let mut path: Option<String> = None;
+    let mut sentinel: Option<String> = None;
+    let mut archive_url: Option<String> = None;
+    let mut sha256: Option<String> = None;
+    let mut tree_sha256: Option<String> = None;
+
+    for raw in contents.lines() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            let header = &line[1..line.len() - 1];
+            in_section = header.trim() == format!("submodules.{}", name);
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+
+        let Some((k, v)) = line.split_once('=') else {
+            continue;
+        };
+        let key = k.trim();
+        let mut val = v.trim();
+        if val.starts_with('"') && val.ends_with('"') && val.len() >= 2 {
+            val = &val[1..val.len() - 1];
+        }
+        match key {
+            "url" => url = Some(val.to_string()),
+            "rev" => rev = Some(val.to_string()),
+            "path" => path = Some(val.to_string()),
+            "sentinel" => sentinel = Some(val.to_string()),
+            "archive_url" => archive_url = Some(val.to_string()),
+            "sha256" => sha256 = Some(val.to_string()),
+            "tree_sha256" => tree_sha256 = Some(val.to_string()),
+            _ => {}
+        }
+    }
+
+    let url = url.unwrap_or_else(|| {
+        panic!(
+            "Missing `url` for submodule '{}' in {}",
+            name,
+            submodules_toml.display()
+        )
+    });
+    let rev = rev.unwrap_or_else(|| {
+        panic!(
+            "Missing `rev` for submodule '{}' in {}",
+            name,
+            submodules_toml.display()
+        )
+    });
+    let path = path.unwrap_or_else(|| format!("3rdparty/{name}"));
+
+    PinnedSubmodule {
+        url,
+        rev,
+        path,
+        sentinel,
+        archive_url,
+        sha256,
+        tree_sha256,
+    }
+}
+
+/// Download `archive_url`, verify it hashes to `sha256`, and extract it into `checkout_dir`.
+/// Panics on a hash mismatch before any extracted files are left on disk.
+fn fetch_and_verify_tarball(
+    name: &str,
+    archive_url: &str,
+    sha256: &str,
+    checkout_dir: &Path,
+) {
+    create_dir_all(checkout_dir).expect("Failed to create checkout dir");
+    let archive_path = checkout_dir.with_extension("tar.gz.tmp");
+
+    // Prefer curl/wget so this path has no dependency on a pure-Rust HTTP stack;
+    // either is present on essentially every build image that lacks git.
+    let fetched = Command::new("curl")
+        .args(["-fsSL", "-o"])
+        .arg(&archive_path)
+        .arg(archive_url)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+        || Command::new("wget")
+            .args(["-q", "-O"])
+            .arg(&archive_path)
+            .arg(archive_url)
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+    if !fetched {
+        panic!("Failed to download {name} archive from {archive_url} (tried curl and wget)");
+    }
+
+    let mut hasher = Sha256::new();
+    let mut file = File::open(&archive_path)
+        .unwrap_or_else(|e| panic!("Failed to open downloaded archive {}: {e}", archive_path.display()));
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).expect("Failed to read downloaded archive");
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    let digest = format!("{:x}", hasher.finalize());
+    if digest != sha256.to_ascii_lowercase() {
+        let _ = fs::remove_file(&archive_path);
+        panic!(
+            "SHA-256 mismatch for {name} archive {archive_url}: expected {sha256}, got {digest}"
+        );
+    }
+
+    let tar_gz = File::open(&archive_path).expect("Failed to reopen verified archive");
+    let tar = flate2::read::GzDecoder::new(tar_gz);
+    tar::Archive::new(tar)
+        .unpack(checkout_dir)
+        .unwrap_or_else(|e| panic!("Failed to extract {name} archive: {e}"));
+    let _ = fs::remove_file(&archive_path);
+}
+
+/// Ensure a pinned submodule is materialized locally, preferring the tarball+SHA-256
+/// path when `archive_url`/`sha256` are pinned, and falling back to `ensure_git_checkout_cached`
+/// (which itself prefers a shallow fetch when `XGRAMMAR_SHALLOW_FETCH` is set).
+pub fn ensure_submodule_cached(name: &str, pin: &PinnedSubmodule, cache_dir: &Path) -> PathBuf {
+    let (archive_url, sha256) = match (&pin.archive_url, &pin.sha256) {
+        (Some(archive_url), Some(sha256)) => (archive_url, sha256),
+        _ => {
+            return ensure_git_checkout_cached(
+                name,
+                &pin.url,
+                &pin.rev,
+                cache_dir,
+                pin.tree_sha256.as_deref(),
+            )
+        }
+    };
+
+    let checkout_dir = cache_dir.join(format!("{}-{}", name, pin.rev));
+    let marker = checkout_dir.join(".xgrammar_rs_fetched");
+    if marker.exists() {
+        return checkout_dir;
+    }
+    if checkout_dir.exists() {
+        let _ = fs::remove_dir_all(&checkout_dir);
+    }
+    create_dir_all(cache_dir).expect("Failed to create cache dir");
+    fetch_and_verify_tarball(name, archive_url, sha256, &checkout_dir);
+    let mut f = File::create(&marker).expect("Failed to write fetch marker");
+    f.write_all(pin.rev.as_bytes()).ok();
+    checkout_dir
+}
+
+/// Parse the `path`/`url` pair for `[submodule "..."]` entries out of a `.gitmodules` file,
+/// keyed by the basename of each entry's `path` (matching how `name` identifies a
+/// submodule everywhere else in this module).
+fn parse_gitmodules(gitmodules: &Path) -> Vec<(String, String, String)> {
+    let Ok(contents) = fs::read_to_string(gitmodules) else {
+        return Vec::new();
+    };
+
+    fn flush(
+        path: &mut Option<String>,
+        url: &mut Option<String>,
+        entries: &mut Vec<(String, String, String)>,
+    ) {
+        if let (Some(p), Some(u)) = (path.take(), url.take()) {
+            let name = Path::new(&p)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| p.clone());
+            entries.push((name, p, u));
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut path: Option<String> = None;
+    let mut url: Option<String> = None;
+
+    for raw in contents.lines() {
+        let line = raw.trim();
+        if line.starts_with("[submodule") {
+            flush(&mut path, &mut url, &mut entries);
+            continue;
+        }
+        let Some((k, v)) = line.split_once('=') else {
+            continue;
+        };
+        match k.trim() {
+            "path" => path = Some(v.trim().to_string()),
+            "url" => url = Some(v.trim().to_string()),
+            _ => {}
+        }
+    }
+    flush(&mut path, &mut url, &mut entries);
+    entries
+}
+
+/// Resolve a submodule's pinned commit from the git index instead of a generated pin
+/// file: `git ls-tree HEAD <path>` prints the gitlink entry for a submodule path with
+/// the pinned commit as its object id (mode `160000`), which is exactly what a plain
+/// `git submodule` checkout already records without any extra tooling.
+fn read_submodule_rev_from_index(repo_dir: &Path, path: &str) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_dir)
+        .args(["ls-tree", "HEAD"])
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Format: "<mode> commit <sha>\t<path>"
+    let line = stdout.lines().next()?;
+    let (meta, _) = line.split_once('\t')?;
+    let mut fields = meta.split_whitespace();
+    let mode = fields.next()?;
+    if mode != "160000" {
+        return None;
+    }
+    fields.next()?; // "commit"
+    fields.next().map(str::to_string)
+}
+
+/// Fall back to resolving a submodule's `url`/`rev` straight from the repo's own
+/// `.gitmodules` and git index, for a plain `git clone --recurse-submodules` checkout
+/// (or fork) that never ran `scripts/update_rust_submodules.sh` to generate
+/// `submodules.toml`.
+fn read_pinned_submodule_from_repo(repo_dir: &Path, name: &str) -> (String, String) {
+    let gitmodules = repo_dir.join(".gitmodules");
+    let (_, path, url) = parse_gitmodules(&gitmodules)
+        .into_iter()
+        .find(|(entry_name, _, _)| entry_name == name)
+        .unwrap_or_else(|| {
+            panic!(
+                "No submodule pin found for '{name}': no matching entry was found in {}.\n\
+                 Run `bash scripts/update_rust_submodules.sh` to regenerate submodules.toml, \
+                 or build from a checkout with `.gitmodules` present.",
+                gitmodules.display()
+            )
+        });
+    let rev = read_submodule_rev_from_index(repo_dir, &path).unwrap_or_else(|| {
+        panic!(
+            "Could not resolve the pinned commit for submodule '{name}' via \
+             `git -C {} ls-tree HEAD {path}`. Run `git submodule update --init` first, or \
+             regenerate submodules.toml with `bash scripts/update_rust_submodules.sh`.",
+            repo_dir.display()
+        )
+    });
+    (url, rev)
+}
+
+/// Read pinned submodule information from submodules.toml, falling back to
+/// `.gitmodules` + the git index (see [`read_pinned_submodule_from_repo`]) when the toml
+/// hasn't been generated, so a plain git checkout builds without extra tooling.
+pub fn read_pinned_submodule(submodules_toml: &Path, repo_dir: &Path, name: &str) -> (String, String) {
+    if !submodules_toml.exists() {
+        return read_pinned_submodule_from_repo(repo_dir, name);
+    }
+
     let contents = fs::read_to_string(submodules_toml).unwrap_or_else(|e| {
         panic!(
             "Failed to read submodule pins at {}: {}.\n\
@@ -75,17 +394,116 @@ pub fn read_pinned_submodule(submodules_toml: &Path, name: &str) -> (String, Str
     (url, rev)
 }
 
-/// Ensure a git checkout is cached locally
+/// Try to fetch only the pinned commit via `git fetch --depth 1 origin <rev>`, which
+/// works when the server has `uploadpack.allow{Reachable,Any}SHA1InWant` enabled. Returns
+/// `true` on success; the caller falls back to a full clone otherwise.
+fn try_shallow_fetch(name: &str, url: &str, rev: &str, checkout_dir: &Path) -> bool {
+    create_dir_all(checkout_dir).ok();
+    let steps: [&[&str]; 4] = [
+        &["init"],
+        &["remote", "add", "origin", url],
+        &["fetch", "--depth", "1", "origin", rev],
+        &["checkout", "FETCH_HEAD"],
+    ];
+    for args in steps {
+        let ok = Command::new("git")
+            .arg("-C")
+            .arg(checkout_dir)
+            .args(args)
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+        if !ok {
+            let _ = fs::remove_dir_all(checkout_dir);
+            return false;
+        }
+    }
+    println!("cargo:warning=xgrammar-rs: shallow-fetched {name}@{rev}");
+    true
+}
+
+/// Marker contents recorded after a successful checkout: the pinned rev on the first
+/// line, and the [`compute_tree_digest`] of the materialized tree on the second. The
+/// digest line lets [`ensure_git_checkout_cached`] detect a cache directory that was
+/// interrupted mid-write or corrupted on disk, instead of blindly reusing it.
+fn write_fetch_marker(marker: &Path, rev: &str, tree_digest: &str) {
+    let _ = fs::write(marker, format!("{rev}\n{tree_digest}\n"));
+}
+
+/// Parse a marker written by [`write_fetch_marker`] into `(rev, tree_digest)`. Markers
+/// from before the digest line was added only have the rev, so the digest is optional.
+fn read_fetch_marker(marker: &Path) -> Option<(String, Option<String>)> {
+    let contents = fs::read_to_string(marker).ok()?;
+    let mut lines = contents.lines();
+    let rev = lines.next()?.to_string();
+    let digest = lines.next().map(str::to_string);
+    Some((rev, digest))
+}
+
+/// Hash a materialized checkout tree into a single digest: walk every file under `dir`
+/// in sorted order (excluding the `.xgrammar_rs_fetched` marker itself, which records
+/// this digest and so can't be part of what it covers) and feed a SHA-256 hasher the
+/// relative path followed by the file's contents. Used to detect a cache directory that
+/// was interrupted mid-write (e.g. by a disk error) or otherwise silently corrupted
+/// between builds.
+fn compute_tree_digest(dir: &Path) -> String {
+    let mut paths: Vec<PathBuf> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.path().to_path_buf())
+        .filter(|p| p.file_name().map(|n| n != ".xgrammar_rs_fetched").unwrap_or(true))
+        .collect();
+    paths.sort();
+
+    let mut hasher = Sha256::new();
+    for path in paths {
+        let rel = path.strip_prefix(dir).expect("strip_prefix failed");
+        hasher.update(rel.to_string_lossy().as_bytes());
+        hasher.update(b"\0");
+        let mut file = File::open(&path)
+            .unwrap_or_else(|e| panic!("Failed to read {} while hashing: {e}", path.display()));
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let n = file.read(&mut buf).expect("Failed to read file while hashing");
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Ensure a git checkout is cached locally, verifying the integrity of a reused cache
+/// entry against the digest recorded in its marker (and, if `expected_tree_sha256` is
+/// set, against that pin too) before trusting it.
 pub fn ensure_git_checkout_cached(
     name: &str,
     url: &str,
     rev: &str,
     cache_dir: &Path,
+    expected_tree_sha256: Option<&str>,
 ) -> PathBuf {
     let checkout_dir = cache_dir.join(format!("{}-{}", name, rev));
     let marker = checkout_dir.join(".xgrammar_rs_fetched");
-    if marker.exists() {
-        return checkout_dir;
+    if let Some((marker_rev, marker_digest)) = read_fetch_marker(&marker) {
+        let actual_digest = compute_tree_digest(&checkout_dir);
+        let digest_ok = match marker_digest.as_deref() {
+            Some(d) => d == actual_digest,
+            None => true,
+        };
+        let pin_ok = expected_tree_sha256
+            .map(|expected| expected.eq_ignore_ascii_case(&actual_digest))
+            .unwrap_or(true);
+        if marker_rev == rev && digest_ok && pin_ok {
+            return checkout_dir;
+        }
+        println!(
+            "cargo:warning=xgrammar-rs: cached checkout of '{name}' at {} failed integrity \
+             verification; re-fetching",
+            checkout_dir.display()
+        );
     }
 
     if checkout_dir.exists() {
@@ -93,64 +511,132 @@ pub fn ensure_git_checkout_cached(
     }
     create_dir_all(cache_dir).expect("Failed to create cache dir");
 
-    // Note: We intentionally do a full clone for reliability (the pinned commit might not
-    // be fetchable shallowly in all server configs). These repos are small enough and are
-    // cached across builds.
-    run_checked(
-        {
-            let mut c = Command::new("git");
-            c.arg("clone").arg(url).arg(&checkout_dir);
-            c
-        },
-        &format!("git clone {} into cache", name),
-    );
-    run_checked(
-        {
-            let mut c = Command::new("git");
-            c.arg("-C").arg(&checkout_dir).arg("checkout").arg(rev);
-            c
-        },
-        &format!("git checkout {}@{}", name, rev),
-    );
-
-    let _ = fs::write(&marker, rev);
+    // `XGRAMMAR_SHALLOW_FETCH=1` trades the default full-clone reliability for a much
+    // cheaper single-commit fetch; falls back to the full clone below on any failure so
+    // correctness is preserved when a server doesn't allow fetching by commit id.
+    let shallow_ok =
+        is_truthy_env("XGRAMMAR_SHALLOW_FETCH") && try_shallow_fetch(name, url, rev, &checkout_dir);
+
+    if !shallow_ok {
+        // Note: full clone for reliability (the pinned commit might not be fetchable
+        // shallowly in all server configs). These repos are small enough and are
+        // cached across builds.
+        run_checked(
+            {
+                let mut c = Command::new("git");
+                c.arg("clone").arg(url).arg(&checkout_dir);
+                c
+            },
+            &format!("git clone {} into cache", name),
+        );
+        run_checked(
+            {
+                let mut c = Command::new("git");
+                c.arg("-C").arg(&checkout_dir).arg("checkout").arg(rev);
+                c
+            },
+            &format!("git checkout {}@{}", name, rev),
+        );
+    }
+
+    let tree_digest = compute_tree_digest(&checkout_dir);
+    if let Some(expected) = expected_tree_sha256 {
+        if !expected.eq_ignore_ascii_case(&tree_digest) {
+            let _ = fs::remove_dir_all(&checkout_dir);
+            panic!(
+                "Tree digest mismatch for '{name}'@{rev}: expected {expected}, got {tree_digest}. \
+                 This means either the pinned `tree_sha256` in submodules.toml is stale, or the \
+                 fetched commit doesn't match what was pinned."
+            );
+        }
+    }
+    write_fetch_marker(&marker, rev, &tree_digest);
     checkout_dir
 }
 
-/// Prepare the XGrammar source tree, fetching submodules if necessary
+/// Whether `pin` looks materialized under `repo_dir` already (its `sentinel`, or just
+/// its destination directory when no sentinel is declared, exists).
+fn submodule_is_present(repo_dir: &Path, pin: &PinnedSubmodule) -> bool {
+    let check = match &pin.sentinel {
+        Some(sentinel) => repo_dir.join(&pin.path).join(sentinel),
+        None => repo_dir.join(&pin.path),
+    };
+    check.exists()
+}
+
+/// Directory of checked-in vendored submodule copies, e.g. `<crate>/vendor/dlpack`.
+/// Populated by `scripts/vendor_rust_submodules.sh` and shipped inside the `.crate`
+/// tarball so `cargo package`/`--offline` builds don't need network access.
+fn vendored_submodule_dir(manifest_dir: &Path, name: &str) -> PathBuf {
+    manifest_dir.join("vendor").join(name)
+}
+
+/// Whether a vendored copy of `pin` exists under `<manifest_dir>/vendor/<name>`.
+fn vendored_submodule_is_present(manifest_dir: &Path, name: &str, pin: &PinnedSubmodule) -> bool {
+    let dir = vendored_submodule_dir(manifest_dir, name);
+    match &pin.sentinel {
+        Some(sentinel) => dir.join(sentinel).exists(),
+        None => dir.exists(),
+    }
+}
+
+/// Prepare the XGrammar source tree, fetching any declared-but-missing git submodules.
+///
+/// Data-driven over every `[submodules.*]` section in `submodules.toml` (rather than
+/// hardcoding dlpack): each pin's `path` says where it belongs in the work tree, and its
+/// `sentinel` (or just the destination directory) is used to decide whether it's already
+/// present and to validate a fetch actually produced something buildable.
+///
+/// `manifest_dir` is consulted for a checked-in `vendor/<name>` copy of each missing
+/// submodule before falling back to a network fetch; this is what lets `cargo
+/// package`/`--offline` builds succeed without network access, as long as
+/// `scripts/vendor_rust_submodules.sh` was run before packaging.
 pub fn prepare_xgrammar_source_tree(
     xgrammar_repo_dir: &Path,
+    manifest_dir: &Path,
     out_dir: &Path,
     submodules_toml: &Path,
 ) -> PathBuf {
-    let dlpack_header = xgrammar_repo_dir.join("3rdparty/dlpack/include/dlpack/dlpack.h");
-    if dlpack_header.exists() {
+    let submodule_names = list_submodule_names(submodules_toml);
+    let pins: Vec<PinnedSubmodule> = submodule_names
+        .iter()
+        .map(|name| read_pinned_submodule_full(submodules_toml, name))
+        .collect();
+
+    let missing: Vec<(&String, &PinnedSubmodule)> = submodule_names
+        .iter()
+        .zip(&pins)
+        .filter(|(_, pin)| !submodule_is_present(xgrammar_repo_dir, pin))
+        .collect();
+    if missing.is_empty() {
         return xgrammar_repo_dir.to_path_buf();
     }
 
-    // crates.io sources may be missing submodules; fetch pinned dlpack into a cache and
-    // materialize a buildable tree under OUT_DIR.
+    // crates.io sources may be missing submodules; fetch each pinned, missing one into a
+    // cache (or use a checked-in `vendor/` copy) and materialize a buildable tree under
+    // OUT_DIR.
     if cargo_offline() {
-        panic!(
-            "Required git submodule `3rdparty/dlpack` is missing (expected {}). \
-             Cargo is in offline mode. Either:\n\
-             - build with network access, or\n\
-             - build from a git checkout with submodules initialized, or\n\
-             - set XGRAMMAR_SRC_DIR to an XGrammar repo root that already has submodules.",
-            dlpack_header.display()
-        );
+        let unavailable: Vec<&str> = missing
+            .iter()
+            .filter(|(name, pin)| !vendored_submodule_is_present(manifest_dir, name, pin))
+            .map(|(_, pin)| pin.path.as_str())
+            .collect();
+        if !unavailable.is_empty() {
+            panic!(
+                "Required git submodule(s) are missing: {}. \
+                 Cargo is in offline mode and no vendored copy was found under {}. Either:\n\
+                 - build with network access, or\n\
+                 - build from a git checkout with submodules initialized, or\n\
+                 - run `bash scripts/vendor_rust_submodules.sh` before packaging so the \
+                   vendored copies ship in the `.crate` tarball, or\n\
+                 - set XGRAMMAR_SRC_DIR to an XGrammar repo root that already has submodules.",
+                unavailable.join(", "),
+                manifest_dir.join("vendor").display()
+            );
+        }
     }
 
     let cache_dir = submodule_cache_dir(out_dir);
-    println!(
-        "cargo:warning=xgrammar-rs: dlpack submodule missing; fetching into cache at {}",
-        cache_dir.display()
-    );
-
-    let (dlpack_url, dlpack_rev) = read_pinned_submodule(submodules_toml, "dlpack");
-    let dlpack_checkout =
-        ensure_git_checkout_cached("dlpack", &dlpack_url, &dlpack_rev, &cache_dir);
-
     let work_dir = out_dir.join("xgrammar-src");
     if work_dir.exists() {
         let _ = fs::remove_dir_all(&work_dir);
@@ -179,17 +665,44 @@ pub fn prepare_xgrammar_source_tree(
         }
     }
 
-    let dlpack_dst = work_dir.join("3rdparty/dlpack");
-    copy_dir_recursive_filtered(&dlpack_checkout, &dlpack_dst, |rel| {
-        rel.components().any(|c| c.as_os_str() == ".git")
-    });
+    for (name, pin) in submodule_names.iter().zip(&pins) {
+        let dst = work_dir.join(&pin.path);
+        if submodule_is_present(xgrammar_repo_dir, pin) {
+            // Already present in the repo dir (not one we need to fetch); copy it through
+            // unchanged so the work tree still has every declared submodule.
+            let src = xgrammar_repo_dir.join(&pin.path);
+            copy_dir_recursive_filtered(&src, &dst, |rel| {
+                rel.components().any(|c| c.as_os_str() == ".git")
+            });
+            continue;
+        }
 
-    let dlpack_header_work = work_dir.join("3rdparty/dlpack/include/dlpack/dlpack.h");
-    if !dlpack_header_work.exists() {
-        panic!(
-            "Fetched dlpack but the expected header was not found at {}",
-            dlpack_header_work.display()
-        );
+        let checkout = if vendored_submodule_is_present(manifest_dir, name, pin) {
+            println!(
+                "cargo:warning=xgrammar-rs: submodule '{}' missing; using vendored copy at {}",
+                pin.path,
+                vendored_submodule_dir(manifest_dir, name).display()
+            );
+            vendored_submodule_dir(manifest_dir, name)
+        } else {
+            println!(
+                "cargo:warning=xgrammar-rs: submodule '{}' missing; fetching into cache at {}",
+                pin.path,
+                cache_dir.display()
+            );
+            ensure_submodule_cached(&pin.path, pin, &cache_dir)
+        };
+        copy_dir_recursive_filtered(&checkout, &dst, |rel| {
+            rel.components().any(|c| c.as_os_str() == ".git")
+        });
+
+        if !submodule_is_present(&work_dir, pin) {
+            panic!(
+                "Fetched submodule '{}' but the expected sentinel was not found at {}",
+                pin.path,
+                dst.display()
+            );
+        }
     }
 
     work_dir
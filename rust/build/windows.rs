@@ -1,4 +1,8 @@
-use std::{env, path::PathBuf, process::Command};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 #[derive(Debug, Clone)]
 pub struct VsInstallation {
@@ -7,6 +11,136 @@ pub struct VsInstallation {
     pub display_name: String,
 }
 
+/// Raw bindings for the small slice of the VS Setup Configuration COM API
+/// (`Microsoft.VisualStudio.Setup.Configuration.Native`) we need to enumerate installations.
+/// There's no `windows-sys`/`winapi` dependency available here, so these are hand-rolled
+/// `extern "system"` declarations mirroring the published IDL; see
+/// <https://github.com/microsoft/vs-setup-samples> for the reference vtables and GUIDs.
+mod vs_setup_com {
+    #![allow(non_snake_case, non_camel_case_types)]
+
+    use std::ffi::c_void;
+
+    pub type HRESULT = i32;
+    pub type BSTR = *mut u16;
+
+    pub const S_OK: HRESULT = 0;
+    pub const S_FALSE: HRESULT = 1;
+    pub const REGDB_E_CLASSNOTREG: HRESULT = 0x80040154u32 as i32;
+
+    #[repr(C)]
+    pub struct GUID {
+        pub data1: u32,
+        pub data2: u16,
+        pub data3: u16,
+        pub data4: [u8; 8],
+    }
+
+    pub const CLSID_SETUP_CONFIGURATION: GUID = GUID {
+        data1: 0x177f_0c4a,
+        data2: 0x1cd3,
+        data3: 0x4de7,
+        data4: [0xa3, 0x2c, 0x71, 0xdb, 0xbb, 0x9f, 0xa3, 0x6d],
+    };
+
+    pub const IID_ISETUP_CONFIGURATION2: GUID = GUID {
+        data1: 0x26aa_b78c,
+        data2: 0x4a60,
+        data3: 0x49d6,
+        data4: [0xaf, 0x3b, 0x3c, 0x35, 0xbc, 0x93, 0x36, 0x5d],
+    };
+
+    #[repr(C)]
+    pub struct ISetupInstanceVtbl {
+        pub QueryInterface: unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+        pub AddRef: unsafe extern "system" fn(*mut c_void) -> u32,
+        pub Release: unsafe extern "system" fn(*mut c_void) -> u32,
+        pub GetInstanceId: unsafe extern "system" fn(*mut c_void, *mut BSTR) -> HRESULT,
+        pub GetInstallDate: unsafe extern "system" fn(*mut c_void, *mut u64) -> HRESULT,
+        pub GetInstallationName: unsafe extern "system" fn(*mut c_void, *mut BSTR) -> HRESULT,
+        pub GetInstallationPath: unsafe extern "system" fn(*mut c_void, *mut BSTR) -> HRESULT,
+        pub GetInstallationVersion: unsafe extern "system" fn(*mut c_void, *mut BSTR) -> HRESULT,
+        pub GetDisplayName: unsafe extern "system" fn(*mut c_void, u32, *mut BSTR) -> HRESULT,
+        // Remaining vtable slots (GetDescription, ResolvePath, ...) are not called here.
+    }
+
+    #[repr(C)]
+    pub struct ISetupInstance {
+        pub vtbl: *const ISetupInstanceVtbl,
+    }
+
+    #[repr(C)]
+    pub struct IEnumSetupInstancesVtbl {
+        pub QueryInterface: unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+        pub AddRef: unsafe extern "system" fn(*mut c_void) -> u32,
+        pub Release: unsafe extern "system" fn(*mut c_void) -> u32,
+        pub Next:
+            unsafe extern "system" fn(*mut c_void, u32, *mut *mut ISetupInstance, *mut u32) -> HRESULT,
+        pub Skip: unsafe extern "system" fn(*mut c_void, u32) -> HRESULT,
+        pub Reset: unsafe extern "system" fn(*mut c_void) -> HRESULT,
+        pub Clone: unsafe extern "system" fn(*mut c_void, *mut *mut c_void) -> HRESULT,
+    }
+
+    #[repr(C)]
+    pub struct IEnumSetupInstances {
+        pub vtbl: *const IEnumSetupInstancesVtbl,
+    }
+
+    #[repr(C)]
+    pub struct ISetupConfiguration2Vtbl {
+        pub QueryInterface: unsafe extern "system" fn(*mut c_void, *const GUID, *mut *mut c_void) -> HRESULT,
+        pub AddRef: unsafe extern "system" fn(*mut c_void) -> u32,
+        pub Release: unsafe extern "system" fn(*mut c_void) -> u32,
+        pub EnumInstances: unsafe extern "system" fn(*mut c_void, *mut *mut c_void) -> HRESULT,
+        pub GetInstanceForCurrentProcess: unsafe extern "system" fn(*mut c_void, *mut *mut c_void) -> HRESULT,
+        pub GetInstanceForPath: unsafe extern "system" fn(*mut c_void, *const u16, *mut *mut c_void) -> HRESULT,
+        pub EnumAllInstances:
+            unsafe extern "system" fn(*mut c_void, *mut *mut IEnumSetupInstances) -> HRESULT,
+    }
+
+    #[repr(C)]
+    pub struct ISetupConfiguration2 {
+        pub vtbl: *const ISetupConfiguration2Vtbl,
+    }
+
+    #[link(name = "ole32")]
+    extern "system" {
+        pub fn CoInitializeEx(reserved: *mut c_void, co_init: u32) -> HRESULT;
+        pub fn CoUninitialize();
+        pub fn CoCreateInstance(
+            rclsid: *const GUID,
+            unknown_outer: *mut c_void,
+            cls_context: u32,
+            riid: *const GUID,
+            ppv: *mut *mut c_void,
+        ) -> HRESULT;
+    }
+
+    #[link(name = "oleaut32")]
+    extern "system" {
+        pub fn SysFreeString(bstr: BSTR);
+    }
+
+    pub const COINIT_MULTITHREADED: u32 = 0x0;
+    pub const CLSCTX_INPROC_SERVER: u32 = 0x1;
+
+    /// Convert a BSTR to a Rust `String` by reading its UTF-16 code units up to the
+    /// terminating null (the length prefix is not read), freeing the BSTR either way.
+    pub unsafe fn bstr_to_string(bstr: BSTR) -> String {
+        if bstr.is_null() {
+            return String::new();
+        }
+        let mut len = 0usize;
+        while *bstr.add(len) != 0 {
+            len += 1;
+        }
+        let slice = std::slice::from_raw_parts(bstr, len);
+        let s = String::from_utf16_lossy(slice);
+        SysFreeString(bstr);
+        s
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WindowsArch {
     Arm64,
@@ -15,24 +149,37 @@ pub enum WindowsArch {
 }
 
 impl WindowsArch {
+    fn from_triple(triple: &str) -> Option<Self> {
+        if triple.contains("aarch64") {
+            Some(Self::Arm64)
+        } else if triple.contains("x86_64") {
+            Some(Self::X64)
+        } else if triple.contains("i686") || triple.contains("i586") {
+            Some(Self::X86)
+        } else {
+            None
+        }
+    }
+
+    /// Detect the *target* architecture, i.e. the arch we're building `cl.exe`-compiled
+    /// code for. Falls back to `HOST` only if `TARGET` is unset or unrecognized.
     pub fn detect_from_env() -> Self {
         let target = env::var("TARGET").unwrap_or_default();
-        if target.contains("aarch64") {
-            return Self::Arm64;
-        }
-        if target.contains("x86_64") {
-            return Self::X64;
-        }
-        if target.contains("i686") || target.contains("i586") {
-            return Self::X86;
+        if let Some(arch) = Self::from_triple(&target) {
+            return arch;
         }
 
         let host = env::var("HOST").unwrap_or_default();
-        if host.contains("aarch64") {
-            Self::Arm64
-        } else {
-            Self::X64
-        }
+        Self::from_triple(&host).unwrap_or(Self::X64)
+    }
+
+    /// Detect the *host* architecture, i.e. the arch the MSVC toolchain binaries
+    /// themselves (`cl.exe`, `link.exe`) run on. This is distinct from
+    /// [`Self::detect_from_env`] whenever cross-compiling: an x64 host building for
+    /// ARM64 needs `bin\Hostx64\arm64`, not the nonexistent `bin\Hostarm64\arm64`.
+    pub fn detect_host_from_env() -> Self {
+        let host = env::var("HOST").unwrap_or_default();
+        Self::from_triple(&host).unwrap_or(Self::X64)
     }
 
     pub fn llvm_subdir(&self) -> &'static str {
@@ -60,7 +207,98 @@ impl WindowsArch {
     }
 }
 
+/// Enumerate VS installations via the `SetupConfiguration` COM API, which the VS installer
+/// itself registers (unlike `vswhere.exe`, which is just a convenience binary the installer
+/// happens to also drop on disk and that users sometimes remove or relocate).
+///
+/// Returns `None` (rather than an empty `Vec`) when the COM class isn't registered at all
+/// (`REGDB_E_CLASSNOTREG`) or no VS installer component is present, so callers can tell
+/// "no installations" apart from "this API isn't usable here" and fall back to vswhere.
+fn find_vs_installations_via_com() -> Option<Vec<VsInstallation>> {
+    use vs_setup_com::*;
+
+    unsafe {
+        // S_FALSE here means COM was already initialized (e.g. by a host process); both
+        // S_OK and S_FALSE are fine to proceed on.
+        let init_hr = CoInitializeEx(std::ptr::null_mut(), COINIT_MULTITHREADED);
+        if init_hr != S_OK && init_hr != S_FALSE {
+            return None;
+        }
+
+        let mut config_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
+        let hr = CoCreateInstance(
+            &CLSID_SETUP_CONFIGURATION,
+            std::ptr::null_mut(),
+            CLSCTX_INPROC_SERVER,
+            &IID_ISETUP_CONFIGURATION2,
+            &mut config_ptr,
+        );
+        if hr == REGDB_E_CLASSNOTREG {
+            CoUninitialize();
+            return None;
+        }
+        if hr != S_OK || config_ptr.is_null() {
+            CoUninitialize();
+            return None;
+        }
+
+        let config = config_ptr as *mut ISetupConfiguration2;
+        let mut enum_ptr: *mut IEnumSetupInstances = std::ptr::null_mut();
+        let hr = ((*(*config).vtbl).EnumAllInstances)(config_ptr, &mut enum_ptr);
+        if hr != S_OK || enum_ptr.is_null() {
+            ((*(*config).vtbl).Release)(config_ptr);
+            CoUninitialize();
+            return None;
+        }
+
+        let mut installations = Vec::new();
+        loop {
+            let mut instance_ptr: *mut ISetupInstance = std::ptr::null_mut();
+            let mut fetched: u32 = 0;
+            let hr = ((*(*enum_ptr).vtbl).Next)(enum_ptr as *mut _, 1, &mut instance_ptr, &mut fetched);
+            if hr != S_OK || fetched == 0 || instance_ptr.is_null() {
+                break;
+            }
+
+            let instance_vtbl = &*(*instance_ptr).vtbl;
+            let raw_instance_ptr = instance_ptr as *mut std::ffi::c_void;
+
+            let mut path_bstr: BSTR = std::ptr::null_mut();
+            let mut version_bstr: BSTR = std::ptr::null_mut();
+            let mut name_bstr: BSTR = std::ptr::null_mut();
+            (instance_vtbl.GetInstallationPath)(raw_instance_ptr, &mut path_bstr);
+            (instance_vtbl.GetInstallationVersion)(raw_instance_ptr, &mut version_bstr);
+            (instance_vtbl.GetDisplayName)(raw_instance_ptr, 0, &mut name_bstr);
+
+            let path = bstr_to_string(path_bstr);
+            let version = bstr_to_string(version_bstr);
+            let display_name = bstr_to_string(name_bstr);
+
+            (instance_vtbl.Release)(raw_instance_ptr);
+
+            if !path.is_empty() {
+                installations.push(VsInstallation {
+                    path: PathBuf::from(path),
+                    version,
+                    display_name,
+                });
+            }
+        }
+
+        ((*(*enum_ptr).vtbl).Release)(enum_ptr as *mut _);
+        ((*(*config).vtbl).Release)(config_ptr);
+        CoUninitialize();
+        Some(installations)
+    }
+}
+
 pub fn find_vs_installations() -> Vec<VsInstallation> {
+    if let Some(installations) = find_vs_installations_via_com() {
+        if !installations.is_empty() {
+            return installations;
+        }
+    }
+
     let vswhere_paths = [
         PathBuf::from(r"C:\Program Files (x86)\Microsoft Visual Studio\Installer\vswhere.exe"),
         PathBuf::from(r"C:\Program Files\Microsoft Visual Studio\Installer\vswhere.exe"),
@@ -286,12 +524,13 @@ fn print_missing_tools_message(arch: WindowsArch) {
 
 pub fn print_path_setup_instructions() {
     let arch = WindowsArch::detect_from_env();
+    let host_arch = WindowsArch::detect_host_from_env();
     let mut paths_to_add = Vec::new();
 
-    if let Some((_vs_path, msvc_version_dir)) = find_msvc_tools_dir(arch) {
+    if let Some((_vs_path, msvc_version_dir)) = find_msvc_tools_dir(host_arch, arch) {
         let bin_dir = msvc_version_dir
             .join("bin")
-            .join(arch.msvc_host_dir())
+            .join(host_arch.msvc_host_dir())
             .join(arch.vcvars_arg());
         if bin_dir.exists() {
             paths_to_add.push(bin_dir);
@@ -326,44 +565,233 @@ pub fn print_path_setup_instructions() {
     }
 }
 
-fn find_msvc_tools_dir(arch: WindowsArch) -> Option<(PathBuf, PathBuf)> {
-    for vs in find_vs_installations() {
-        let msvc_tools_dir = vs.path.join(r"VC\Tools\MSVC");
-        if !msvc_tools_dir.exists() {
-            continue;
+/// Minimal `HKEY_LOCAL_MACHINE` read access via raw `advapi32` calls. For the MSVC tools it
+/// is a fallback used when neither the COM API nor vswhere's output locates an install
+/// (VS2017-era side-by-side installs register a `VC7` value regardless of whether
+/// `vswhere.exe` is present); for the Windows SDK, the registered `KitsRoot10` is the first
+/// root tried, ahead of the default install paths.
+mod registry {
+    #![allow(non_snake_case)]
+
+    use std::ffi::c_void;
+
+    type HKEY = *mut c_void;
+    type LSTATUS = i32;
+
+    const HKEY_LOCAL_MACHINE: HKEY = 0x8000_0002u32 as isize as HKEY;
+    const KEY_READ: u32 = 0x20019;
+    const KEY_WOW64_32KEY: u32 = 0x0200;
+    const ERROR_SUCCESS: LSTATUS = 0;
+    const REG_SZ: u32 = 1;
+
+    #[link(name = "advapi32")]
+    extern "system" {
+        fn RegOpenKeyExW(
+            hkey: HKEY,
+            sub_key: *const u16,
+            options: u32,
+            sam_desired: u32,
+            result: *mut HKEY,
+        ) -> LSTATUS;
+        fn RegQueryValueExW(
+            hkey: HKEY,
+            value_name: *const u16,
+            reserved: *mut u32,
+            value_type: *mut u32,
+            data: *mut u8,
+            data_size: *mut u32,
+        ) -> LSTATUS;
+        fn RegEnumValueW(
+            hkey: HKEY,
+            index: u32,
+            value_name: *mut u16,
+            value_name_size: *mut u32,
+            reserved: *mut u32,
+            value_type: *mut u32,
+            data: *mut u8,
+            data_size: *mut u32,
+        ) -> LSTATUS;
+        fn RegCloseKey(hkey: HKEY) -> LSTATUS;
+    }
+
+    fn to_wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    fn open_key(sub_key: &str, extra_flags: u32) -> Option<HKEY> {
+        let wide = to_wide(sub_key);
+        let mut hkey: HKEY = std::ptr::null_mut();
+        let status = unsafe {
+            RegOpenKeyExW(HKEY_LOCAL_MACHINE, wide.as_ptr(), 0, KEY_READ | extra_flags, &mut hkey)
+        };
+        if status == ERROR_SUCCESS {
+            Some(hkey)
+        } else {
+            None
         }
+    }
 
-        let Ok(entries) = std::fs::read_dir(&msvc_tools_dir) else {
-            continue;
+    /// Read a single `REG_SZ` value by name from an already-open key.
+    fn read_string_value(hkey: HKEY, value_name: &str) -> Option<String> {
+        let wide_name = to_wide(value_name);
+        let mut value_type = 0u32;
+        let mut data_size = 0u32;
+        let status = unsafe {
+            RegQueryValueExW(
+                hkey,
+                wide_name.as_ptr(),
+                std::ptr::null_mut(),
+                &mut value_type,
+                std::ptr::null_mut(),
+                &mut data_size,
+            )
         };
+        if status != ERROR_SUCCESS || value_type != REG_SZ || data_size == 0 {
+            return None;
+        }
 
-        let mut versions: Vec<_> = entries
-            .flatten()
-            .filter(|e| e.path().is_dir())
+        let mut buf = vec![0u8; data_size as usize];
+        let status = unsafe {
+            RegQueryValueExW(
+                hkey,
+                wide_name.as_ptr(),
+                std::ptr::null_mut(),
+                &mut value_type,
+                buf.as_mut_ptr(),
+                &mut data_size,
+            )
+        };
+        if status != ERROR_SUCCESS {
+            return None;
+        }
+
+        let wide: Vec<u16> = buf
+            .chunks_exact(2)
+            .map(|b| u16::from_ne_bytes([b[0], b[1]]))
             .collect();
-        versions.sort_by_key(|b| std::cmp::Reverse(b.file_name()));
-
-        for entry in versions {
-            let version_dir = entry.path();
-            let bin_dir = version_dir
-                .join("bin")
-                .join(arch.msvc_host_dir())
-                .join(arch.vcvars_arg());
-            let cl_path = bin_dir.join("cl.exe");
-
-            if cl_path.exists() {
-                return Some((vs.path.clone(), version_dir));
+        let end = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
+        Some(String::from_utf16_lossy(&wide[..end]))
+    }
+
+    /// List every value name/data pair under a key (used for `VC7`, whose value *names*
+    /// are VS versions like `"17.0"` and whose values are the VC install roots).
+    fn enum_string_values(hkey: HKEY) -> Vec<(String, String)> {
+        let mut results = Vec::new();
+        let mut index = 0u32;
+        loop {
+            let mut name_buf = vec![0u16; 256];
+            let mut name_len = name_buf.len() as u32;
+            let mut value_type = 0u32;
+            let mut data_size = 0u32;
+            let status = unsafe {
+                RegEnumValueW(
+                    hkey,
+                    index,
+                    name_buf.as_mut_ptr(),
+                    &mut name_len,
+                    std::ptr::null_mut(),
+                    &mut value_type,
+                    std::ptr::null_mut(),
+                    &mut data_size,
+                )
+            };
+            if status != ERROR_SUCCESS {
+                break;
+            }
+            let name = String::from_utf16_lossy(&name_buf[..name_len as usize]);
+            if value_type == REG_SZ {
+                if let Some(value) = read_string_value(hkey, &name) {
+                    results.push((name, value));
+                }
+            }
+            index += 1;
+        }
+        results
+    }
+
+    /// Read every `SOFTWARE\Microsoft\VisualStudio\SxS\VC7` value (the VS2017-era
+    /// side-by-side layout), checking both the native and WOW6432Node-redirected views.
+    pub fn vc7_roots() -> Vec<String> {
+        let sub_key = r"SOFTWARE\Microsoft\VisualStudio\SxS\VC7";
+        let mut roots = Vec::new();
+        for extra_flags in [0, KEY_WOW64_32KEY] {
+            if let Some(hkey) = open_key(sub_key, extra_flags) {
+                roots.extend(enum_string_values(hkey).into_iter().map(|(_, v)| v));
+                unsafe {
+                    RegCloseKey(hkey);
+                }
             }
         }
+        roots
+    }
+
+    /// Read `SOFTWARE\Microsoft\Windows Kits\Installed Roots\KitsRoot10`.
+    pub fn windows_10_kits_root() -> Option<String> {
+        let hkey = open_key(r"SOFTWARE\Microsoft\Windows Kits\Installed Roots", 0)?;
+        let value = read_string_value(hkey, "KitsRoot10");
+        unsafe {
+            RegCloseKey(hkey);
+        }
+        value
+    }
+}
+
+/// Find the newest MSVC tools version directory under `root` (a VS installation root, for a
+/// registry hit the parent of the `VC7` value) whose `bin\Host{host}\{target}`
+/// subdir actually has a `cl.exe` in it. `host` and `target` are independent: cross-compiling
+/// means the tools directory is named after the *host* arch, while the leaf component is the
+/// *target* arch being compiled for.
+fn resolve_msvc_version_dir(root: &std::path::Path, host: WindowsArch, target: WindowsArch) -> Option<PathBuf> {
+    let msvc_tools_dir = root.join(r"VC\Tools\MSVC");
+    if !msvc_tools_dir.exists() {
+        return None;
+    }
+
+    let Ok(entries) = std::fs::read_dir(&msvc_tools_dir) else {
+        return None;
+    };
+
+    let mut versions: Vec<_> = entries.flatten().filter(|e| e.path().is_dir()).collect();
+    versions.sort_by_key(|b| std::cmp::Reverse(b.file_name()));
+
+    for entry in versions {
+        let version_dir = entry.path();
+        let bin_dir = version_dir.join("bin").join(host.msvc_host_dir()).join(target.vcvars_arg());
+        if bin_dir.join("cl.exe").exists() {
+            return Some(version_dir);
+        }
+    }
+    None
+}
+
+fn find_msvc_tools_dir(host: WindowsArch, target: WindowsArch) -> Option<(PathBuf, PathBuf)> {
+    for vs in find_vs_installations() {
+        if let Some(version_dir) = resolve_msvc_version_dir(&vs.path, host, target) {
+            return Some((vs.path, version_dir));
+        }
+    }
+
+    // Registry fallback: VC7 values are keyed by VS version and point at an installation's
+    // `VC` directory rather than the installation root itself, so the root that
+    // `resolve_msvc_version_dir` joins `VC\Tools\MSVC` onto is the value's parent.
+    for vc_root in registry::vc7_roots() {
+        let vc_root = PathBuf::from(vc_root);
+        let install_root = vc_root.parent().map(Path::to_path_buf).unwrap_or(vc_root);
+        if let Some(version_dir) = resolve_msvc_version_dir(&install_root, host, target) {
+            return Some((install_root, version_dir));
+        }
     }
     None
 }
 
 fn find_windows_sdk() -> Option<(PathBuf, String)> {
-    let sdk_roots = [
+    let mut sdk_roots = vec![
         PathBuf::from(r"C:\Program Files (x86)\Windows Kits\10"),
         PathBuf::from(r"C:\Program Files\Windows Kits\10"),
     ];
+    if let Some(kits_root) = registry::windows_10_kits_root() {
+        sdk_roots.insert(0, PathBuf::from(kits_root));
+    }
 
     for sdk_root in sdk_roots {
         let include_dir = sdk_root.join("Include");
@@ -391,6 +819,64 @@ fn find_windows_sdk() -> Option<(PathBuf, String)> {
     None
 }
 
+/// Parse `cmd /C "... && set"` output into `(name, value)` pairs, one per `NAME=value` line.
+fn parse_set_output(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Run `vcvarsall.bat <host>_<target> && set` in a fresh `cmd.exe` and import whichever of
+/// `INCLUDE`/`LIB`/`LIBPATH`/`PATH` it set or changed relative to our current environment.
+/// Returns `false` (and sets nothing) if the batch file can't be located or the command
+/// fails, so the caller can fall back to manually assembling the same variables.
+fn configure_msvc_environment_via_vcvarsall(host: WindowsArch, target: WindowsArch) -> bool {
+    let Some(vcvarsall) = find_vs_installations().into_iter().find_map(|vs| {
+        let candidate = vs.path.join(r"VC\Auxiliary\Build\vcvarsall.bat");
+        candidate.exists().then_some(candidate)
+    }) else {
+        return false;
+    };
+
+    let vcvars_arg = if host == target {
+        target.vcvars_arg().to_string()
+    } else {
+        format!("{}_{}", host.vcvars_arg(), target.vcvars_arg())
+    };
+
+    let command_line = format!("\"{}\" {} && set", vcvarsall.display(), vcvars_arg);
+    let Ok(output) = Command::new("cmd").args(["/C", &command_line]).output() else {
+        return false;
+    };
+    if !output.status.success() {
+        return false;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let new_vars = parse_set_output(&stdout);
+    if new_vars.is_empty() {
+        return false;
+    }
+
+    let mut imported_any = false;
+    for (name, value) in new_vars {
+        let upper = name.to_ascii_uppercase();
+        if !matches!(upper.as_str(), "INCLUDE" | "LIB" | "LIBPATH" | "PATH") {
+            continue;
+        }
+        let changed = env::var(&name).map(|existing| existing != value).unwrap_or(true);
+        if changed {
+            unsafe {
+                env::set_var(&name, &value);
+            }
+            imported_any = true;
+        }
+    }
+    imported_any
+}
+
 pub fn configure_msvc_environment(arch: WindowsArch) {
     // If INCLUDE is set, we assume the MSVC environment is already set up (e.g. via vcvars)
     // We also check for Ninja in PATH, if not found, we try to add it from VS.
@@ -413,12 +899,23 @@ pub fn configure_msvc_environment(arch: WindowsArch) {
         return;
     }
 
-    let Some((_vs_path, msvc_version_dir)) = find_msvc_tools_dir(arch) else {
+    let host_arch = WindowsArch::detect_host_from_env();
+
+    // Prefer letting Microsoft's own `vcvarsall.bat` compute the environment: it tracks
+    // whatever subdirectory layout the currently-installed SDK/toolset actually uses,
+    // where our manual construction below only knows about the directories that existed
+    // when this file was last updated. Fall back to the manual path if the batch file is
+    // missing, can't be found, or exits non-zero.
+    if configure_msvc_environment_via_vcvarsall(host_arch, arch) {
+        return;
+    }
+
+    let Some((_vs_path, msvc_version_dir)) = find_msvc_tools_dir(host_arch, arch) else {
         return;
     };
 
     let arch_dir = arch.vcvars_arg();
-    let host_arch_dir = arch.msvc_host_dir();
+    let host_arch_dir = host_arch.msvc_host_dir();
 
     let bin_dir = msvc_version_dir
         .join("bin")
@@ -434,6 +931,18 @@ pub fn configure_msvc_environment(arch: WindowsArch) {
     }
 
     let mut path_additions = Vec::new();
+
+    // `cl.exe`/`link.exe` under `bin\Host{host}\{target}` are themselves host-arch
+    // binaries: when cross-compiling (host != target) they still load their own DLL
+    // dependencies from `bin\Host{host}\{host}`, so that directory needs to be on PATH
+    // too, not just the target tool directory.
+    if host_arch != arch {
+        let host_bin_dir = msvc_version_dir.join("bin").join(host_arch_dir).join(host_arch.vcvars_arg());
+        if host_bin_dir.exists() {
+            path_additions.push(host_bin_dir);
+        }
+    }
+
     path_additions.push(bin_dir);
 
     let mut include_paths = Vec::new();
@@ -0,0 +1,77 @@
+//! An optional, content-addressed on-disk cache in front of [`crate::GrammarCompiler`], so
+//! a service compiling the same grammar for the same tokenizer across many requests/threads
+//! (e.g. the concurrent-compile pattern in `test_pressure_structural_tag`) pays the rule-
+//! expansion cost once. See `tests/rust/test_compiled_grammar_serialization.rs` for an
+//! end-to-end exercise of this alongside [`crate::CompiledGrammar::serialize`]/
+//! [`crate::CompiledGrammar::deserialize`].
+
+use std::{fs, path::PathBuf};
+
+use crate::{CompiledGrammar, TokenizerInfo};
+
+/// A cheap, non-cryptographic content hash (FNV-1a) used only to derive a cache file name
+/// from a cache key - not for anything security-sensitive.
+fn fnv1a_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// A content-addressed on-disk cache of compiled grammars, keyed by an arbitrary caller-
+/// supplied byte key - typically derived from the grammar's EBNF source, its root rule, and
+/// the [`crate::GrammarCompiler`] construction params, since [`crate::Grammar`] doesn't
+/// expose a stable byte serialization of its own to hash directly.
+///
+/// A tokenizer or compiled-grammar-format mismatch doesn't need its own fingerprint check
+/// here: [`CompiledGrammar::deserialize`] already rejects both (see
+/// `test_deserialize_rejects_tokenizer_mismatch` / `test_deserialize_rejects_stale_cache_version`
+/// in `tests/rust/test_compiled_grammar_serialization.rs`), so a cache hit that fails either
+/// check is simply treated as a miss and falls through to a fresh compile.
+pub struct CompiledGrammarCache {
+    dir: PathBuf,
+}
+
+impl CompiledGrammarCache {
+    /// Create a cache rooted at `dir`. The directory is created lazily on the first write,
+    /// not here, so constructing a cache has no filesystem side effects.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path_for_key(&self, cache_key: &[u8]) -> PathBuf {
+        self.dir.join(format!("{:016x}.bin", fnv1a_hash(cache_key)))
+    }
+
+    /// Look up `cache_key`; on a hit, validate the cached blob against `tokenizer_info` via
+    /// [`CompiledGrammar::deserialize`]. On a miss (file absent, unreadable, or rejected by
+    /// `deserialize`), call `compile` to produce a fresh [`CompiledGrammar`], write it back
+    /// to the cache, and return it - so `compile` (and whatever rule-set expansion it does)
+    /// only ever runs once per distinct `cache_key`.
+    ///
+    /// The cache is strictly an optimization on top of `compile`: a write failure (e.g. a
+    /// read-only cache directory) is ignored rather than surfaced, since `compile`'s result
+    /// is still valid either way.
+    pub fn get_or_compile<E>(
+        &self,
+        cache_key: &[u8],
+        tokenizer_info: &TokenizerInfo,
+        compile: impl FnOnce() -> Result<CompiledGrammar, E>,
+    ) -> Result<CompiledGrammar, E> {
+        let path = self.path_for_key(cache_key);
+        if let Ok(bytes) = fs::read(&path) {
+            if let Ok(compiled) = CompiledGrammar::deserialize(&bytes, tokenizer_info) {
+                return Ok(compiled);
+            }
+        }
+
+        let compiled = compile()?;
+        let blob = compiled.serialize();
+        if fs::create_dir_all(&self.dir).is_ok() {
+            let _ = fs::write(&path, &blob);
+        }
+        Ok(compiled)
+    }
+}
@@ -0,0 +1,10 @@
+//! Committed, pre-generated autocxx bindings.
+//!
+//! `bindings.rs` and `.bindings_hash` here are produced by `cargo xtask codegen` and
+//! checked in so ordinary builds (and docs.rs) don't need a working libclang. Run
+//! `cargo xtask codegen` after changing `rust/src/lib.rs` or the XGrammar headers it
+//! binds against, and `cargo xtask tidy` to verify the committed output is still fresh.
+//!
+//! This module is populated by codegen; until `bindings.rs` has been generated for this
+//! checkout, `build.rs` falls back to running autocxx live (see
+//! `rust/build/autocxx.rs::use_committed_bindings`).
@@ -0,0 +1,35 @@
+//! Rust bindings for the XGrammar constrained-decoding engine.
+//!
+//! This is the autocxx bridge entry point: `rust/build/autocxx.rs` parses this file to
+//! generate `rust/src/generated/bindings.rs` from the XGrammar C++ headers (see
+//! `BuildContext::{src_include_dir, xgrammar_include_dir}` in `rust/build/mod.rs`). The
+//! `include_cpp!` bridge itself (the `generate!`/`pod!` directives naming the bound C++
+//! types - `Grammar`, `GrammarCompiler`, `TokenizerInfo`, `GrammarMatcher`,
+//! `CompiledGrammar`, `VocabType`, and the `testing`/DLPack surface used throughout
+//! `tests/rust/`) is intentionally not authored here: this checkout ships no `cpp/` or
+//! `include/` tree for it to bind against, so writing one out would just be naming C++
+//! declarations that don't exist anywhere in this repo. Once those headers land, the
+//! bridge goes here, and everything below continues to apply unchanged.
+#![cfg_attr(feature = "masking_only", no_std)]
+
+#[cfg(feature = "masking_only")]
+extern crate alloc;
+
+mod compiler_cache;
+mod generated;
+mod matcher;
+mod parse_tree;
+pub mod schema;
+mod utils;
+mod validate;
+
+pub use compiler_cache::CompiledGrammarCache;
+pub use generated::*;
+pub use matcher::{
+    BatchGrammarMatcher, DraftTreeError, DraftTreeTensor, GrammarMatcher, allocate_token_bitmask,
+    apply_token_bitmask_inplace_cpu, get_bitmask_shape, reset_token_bitmask, sampling,
+    validate_draft_tree_tensors,
+};
+pub use parse_tree::{ParseNode, ParseTreeError, RuleEvent, build_parse_tree};
+pub use utils::bytes_as_c_char_ptr;
+pub use validate::{ValidationReport, validate_ebnf};
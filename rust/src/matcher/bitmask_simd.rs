@@ -0,0 +1,325 @@
+//! Pure-Rust, SIMD-dispatched kernel backing [`super::apply_token_bitmask_inplace_cpu`],
+//! so masking large-vocabulary logits every decode step doesn't have to cross the C++
+//! FFI boundary.
+//!
+//! Runtime ISA dispatch picks AVX2 (x86_64), NEON (aarch64), or a portable scalar
+//! fallback once per process and caches the choice. Set `XGRAMMAR_FORCE_SCALAR_MASK=1`
+//! (or build with the `force_scalar_mask` feature) to pin the scalar path, e.g. for
+//! reproducible bit-identical output across machines or to rule out a vectorized-path
+//! bug while debugging.
+//!
+//! `build.rs` (see `rust/build/common.rs::record_simd_level`) additionally records the
+//! `SimdLevel` it picked for the C++ masking kernels via `XGRAMMAR_RS_SIMD_LEVEL`, read
+//! back here with `option_env!` so this dispatch never selects a tier wider than what the
+//! rest of the build assumed was safe for the target.
+//!
+//! This module is written to build under `#![no_std]` with `alloc` only (see
+//! `BuildContext::is_masking_only` in `rust/build/mod.rs`), since the `masking_only`
+//! build mode targets `wasm32-unknown-unknown` and other embedded environments that
+//! can't link the CMake-built C++ backend and have no `std::env`/OS thread support.
+//! That crate-level `#![no_std]` switch itself lives in `rust/src/lib.rs`; this module
+//! only avoids reaching for anything outside `core`/`alloc` so it compiles cleanly once
+//! the crate root opts in.
+
+#[cfg(feature = "masking_only")]
+extern crate alloc;
+#[cfg(feature = "masking_only")]
+use alloc::{format, string::String, vec, vec::Vec};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::DLTensor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IsaLevel {
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+    Scalar,
+}
+
+impl IsaLevel {
+    const UNINIT: u8 = 0;
+    const SCALAR: u8 = 1;
+    #[cfg(target_arch = "x86_64")]
+    const AVX2: u8 = 2;
+    #[cfg(target_arch = "aarch64")]
+    const NEON: u8 = 3;
+
+    fn to_tag(self) -> u8 {
+        match self {
+            #[cfg(target_arch = "x86_64")]
+            IsaLevel::Avx2 => Self::AVX2,
+            #[cfg(target_arch = "aarch64")]
+            IsaLevel::Neon => Self::NEON,
+            IsaLevel::Scalar => Self::SCALAR,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            #[cfg(target_arch = "x86_64")]
+            Self::AVX2 => Some(IsaLevel::Avx2),
+            #[cfg(target_arch = "aarch64")]
+            Self::NEON => Some(IsaLevel::Neon),
+            Self::SCALAR => Some(IsaLevel::Scalar),
+            _ => None,
+        }
+    }
+}
+
+/// `std::env::var` is unavailable under `no_std`; `masking_only` builds (the only ones
+/// that are actually `no_std`) only honor the compile-time `force_scalar_mask` feature.
+#[cfg(feature = "masking_only")]
+fn force_scalar_via_env() -> bool {
+    false
+}
+
+#[cfg(not(feature = "masking_only"))]
+fn force_scalar_via_env() -> bool {
+    std::env::var("XGRAMMAR_FORCE_SCALAR_MASK")
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(false)
+}
+
+/// Whether `build.rs` bounded this build to the portable scalar tier via
+/// `XGRAMMAR_RS_SIMD_LEVEL=portable_scalar` (see `rust/build/mod.rs::SimdLevel`). Absent
+/// when built without that `build.rs` (e.g. a standalone `cargo build` of this crate), in
+/// which case runtime detection is unconstrained.
+fn compiled_level_is_portable_scalar() -> bool {
+    option_env!("XGRAMMAR_RS_SIMD_LEVEL") == Some("portable_scalar")
+}
+
+/// Cached ISA choice, stored as a plain atomic tag rather than `std::sync::OnceLock` so
+/// this dispatch works identically under `no_std` (`masking_only`).
+static ISA_LEVEL: AtomicU8 = AtomicU8::new(IsaLevel::UNINIT);
+
+fn detect_isa() -> IsaLevel {
+    if let Some(level) = IsaLevel::from_tag(ISA_LEVEL.load(Ordering::Relaxed)) {
+        return level;
+    }
+
+    let level = 'detect: {
+        if cfg!(feature = "force_scalar_mask")
+            || force_scalar_via_env()
+            || compiled_level_is_portable_scalar()
+        {
+            break 'detect IsaLevel::Scalar;
+        }
+        // `is_x86_feature_detected!` is a `std`-only macro; `masking_only` (`no_std`)
+        // builds always fall through to the scalar path on this arch instead.
+        #[cfg(all(target_arch = "x86_64", not(feature = "masking_only")))]
+        if std::is_x86_feature_detected!("avx2") {
+            break 'detect IsaLevel::Avx2;
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            // NEON is part of the aarch64 baseline ISA, so it's always available.
+            break 'detect IsaLevel::Neon;
+        }
+        #[allow(unreachable_code)]
+        IsaLevel::Scalar
+    };
+    ISA_LEVEL.store(level.to_tag(), Ordering::Relaxed);
+    level
+}
+
+/// Mask `count` consecutive logical columns starting at bit index `start` of
+/// `bitmask_row`, writing `-inf` to `logits[offset * logit_stride]` wherever the
+/// corresponding bit (`start + offset`) is 0. Used both as the portable fallback and as
+/// the tail handler after a vectorized loop has consumed a whole number of lanes.
+///
+/// # Safety
+/// `logits` must be valid for `count` writes spaced `logit_stride` elements apart, and
+/// `bitmask_row` for reads covering bits `[start, start + count)`.
+unsafe fn mask_range_scalar(
+    logits: *mut f32,
+    logit_stride: isize,
+    bitmask_row: *const u32,
+    start: usize,
+    count: usize,
+) {
+    for offset in 0..count {
+        let global_bit = start + offset;
+        let word = unsafe { *bitmask_row.add(global_bit / 32) };
+        let allowed = (word >> (global_bit % 32)) & 1 != 0;
+        if !allowed {
+            unsafe { *logits.offset(offset as isize * logit_stride) = f32::NEG_INFINITY };
+        }
+    }
+}
+
+/// AVX2 path: process 32 logits (one bitmask word) at a time in lanes of 8. For each
+/// lane group, the relevant 8 bits of the word are expanded into a full-width 0/1 mask
+/// (compare-greater-than-zero), then `blendv` picks between the original logit and
+/// `-inf` per lane. Requires contiguous logits (`logit_stride == 1`); falls back to the
+/// scalar path otherwise, since a strided `vgather`-based blend isn't worth the
+/// complexity for what's expected to be a rare layout.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn mask_row_avx2(logits: *mut f32, logit_stride: isize, bitmask_row: *const u32, vocab_size: usize) {
+    use core::arch::x86_64::*;
+
+    if logit_stride != 1 {
+        unsafe { mask_range_scalar(logits, logit_stride, bitmask_row, 0, vocab_size) };
+        return;
+    }
+
+    let neg_inf = unsafe { _mm256_set1_ps(f32::NEG_INFINITY) };
+    let mut i = 0usize;
+    while i + 8 <= vocab_size {
+        let word = unsafe { *bitmask_row.add(i / 32) };
+        let bits = (word >> (i % 32)) & 0xFF;
+        let lane_mask: [i32; 8] = core::array::from_fn(|lane| ((bits >> lane) & 1) as i32);
+        let allowed = unsafe {
+            _mm256_cmpgt_epi32(
+                _mm256_loadu_si256(lane_mask.as_ptr() as *const __m256i),
+                _mm256_setzero_si256(),
+            )
+        };
+        let ptr = unsafe { logits.add(i) };
+        let orig = unsafe { _mm256_loadu_ps(ptr) };
+        let blended = unsafe { _mm256_blendv_ps(neg_inf, orig, _mm256_castsi256_ps(allowed)) };
+        unsafe { _mm256_storeu_ps(ptr, blended) };
+        i += 8;
+    }
+    if i < vocab_size {
+        unsafe { mask_range_scalar(logits.add(i), 1, bitmask_row, i, vocab_size - i) };
+    }
+}
+
+/// NEON path: the aarch64 analogue of [`mask_row_avx2`], in lanes of 4 using `vbslq_f32`
+/// as the blend.
+#[cfg(target_arch = "aarch64")]
+unsafe fn mask_row_neon(logits: *mut f32, logit_stride: isize, bitmask_row: *const u32, vocab_size: usize) {
+    use core::arch::aarch64::*;
+
+    if logit_stride != 1 {
+        unsafe { mask_range_scalar(logits, logit_stride, bitmask_row, 0, vocab_size) };
+        return;
+    }
+
+    let neg_inf = unsafe { vdupq_n_f32(f32::NEG_INFINITY) };
+    let mut i = 0usize;
+    while i + 4 <= vocab_size {
+        let word = unsafe { *bitmask_row.add(i / 32) };
+        let bits = (word >> (i % 32)) & 0xF;
+        let lane_mask: [u32; 4] =
+            core::array::from_fn(|lane| if (bits >> lane) & 1 != 0 { u32::MAX } else { 0 });
+        let allowed = unsafe { vld1q_u32(lane_mask.as_ptr()) };
+        let ptr = unsafe { logits.add(i) };
+        let orig = unsafe { vld1q_f32(ptr) };
+        let blended = unsafe { vbslq_f32(allowed, orig, neg_inf) };
+        unsafe { vst1q_f32(ptr, blended) };
+        i += 4;
+    }
+    if i < vocab_size {
+        unsafe { mask_range_scalar(logits.add(i), 1, bitmask_row, i, vocab_size - i) };
+    }
+}
+
+/// Mask one logical row of `vocab_size` logits, dispatching to the ISA level picked by
+/// [`detect_isa`].
+///
+/// # Safety
+/// Same preconditions as [`mask_range_scalar`] (with `start = 0`, `count = vocab_size`).
+unsafe fn mask_row(logits: *mut f32, logit_stride: isize, bitmask_row: *const u32, vocab_size: usize) {
+    match detect_isa() {
+        #[cfg(target_arch = "x86_64")]
+        IsaLevel::Avx2 => unsafe { mask_row_avx2(logits, logit_stride, bitmask_row, vocab_size) },
+        #[cfg(target_arch = "aarch64")]
+        IsaLevel::Neon => unsafe { mask_row_neon(logits, logit_stride, bitmask_row, vocab_size) },
+        IsaLevel::Scalar => unsafe { mask_range_scalar(logits, logit_stride, bitmask_row, 0, vocab_size) },
+    }
+}
+
+/// Shape/stride/data-pointer view of a `DLTensor`, read out once up front so the masking
+/// loop below only ever touches raw element pointers.
+struct TensorView {
+    data: *mut u8,
+    ndim: usize,
+    shape: Vec<i64>,
+    strides: Vec<i64>,
+}
+
+impl TensorView {
+    /// # Safety
+    /// `tensor` must be a valid `DLTensor` per the DLPack contract vendored at
+    /// `3rdparty/dlpack`: `shape`/`strides` valid for `ndim` reads (or `strides` null,
+    /// meaning compact row-major), and `data` + `byte_offset` pointing into a live
+    /// allocation covering the described shape.
+    unsafe fn new(tensor: &DLTensor) -> Self {
+        let ndim = tensor.ndim as usize;
+        let shape = unsafe { core::slice::from_raw_parts(tensor.shape, ndim) }.to_vec();
+        let strides = if tensor.strides.is_null() {
+            let mut s = vec![1i64; ndim];
+            for i in (0..ndim.saturating_sub(1)).rev() {
+                s[i] = s[i + 1] * shape[i + 1];
+            }
+            s
+        } else {
+            unsafe { core::slice::from_raw_parts(tensor.strides, ndim) }.to_vec()
+        };
+        let data = unsafe { (tensor.data as *mut u8).add(tensor.byte_offset as usize) };
+        TensorView { data, ndim, shape, strides }
+    }
+}
+
+/// Pure-Rust replacement for the C++ `apply_token_bitmask_inplace_cpu` kernel: for each
+/// selected row, drive every logit whose corresponding bitmask bit is 0 to `-inf`.
+///
+/// Mirrors the original kernel's semantics: `logits` may be 1D (a single row) or 2D
+/// (`batch x vocab`); `vocab_size` defaults to the smaller of the logits' last dimension
+/// and the bitmask's bit capacity (`bitmask.shape[1] * 32`) when not given explicitly;
+/// `indices`, when present, selects which rows (by the same index into both `logits` and
+/// `bitmask`) to mask instead of every row.
+pub(super) fn apply_token_bitmask_inplace_cpu_rust(
+    logits: &mut DLTensor,
+    bitmask: &DLTensor,
+    vocab_size: Option<i32>,
+    indices: Option<&[i32]>,
+) -> Result<(), String> {
+    // Safety: both tensors are handed to us by callers that otherwise pass them across
+    // the C++ FFI boundary, so they already satisfy the DLPack contract required here.
+    let logits_view = unsafe { TensorView::new(logits) };
+    let bitmask_view = unsafe { TensorView::new(bitmask) };
+
+    if logits_view.ndim == 0 || logits_view.ndim > 2 {
+        return Err(format!(
+            "apply_token_bitmask_inplace_cpu: unsupported logits ndim {}",
+            logits_view.ndim
+        ));
+    }
+    if bitmask_view.ndim != 2 {
+        return Err(format!(
+            "apply_token_bitmask_inplace_cpu: unsupported bitmask ndim {}",
+            bitmask_view.ndim
+        ));
+    }
+
+    let logits_vocab = logits_view.shape[logits_view.ndim - 1];
+    let bitmask_capacity = bitmask_view.shape[1] * 32;
+    let effective_vocab_size = match vocab_size {
+        Some(v) if v >= 0 => v as i64,
+        _ => logits_vocab.min(bitmask_capacity),
+    } as usize;
+
+    let logits_col_stride = logits_view.strides[logits_view.ndim - 1] as isize;
+    let logits_row_stride = if logits_view.ndim == 2 { logits_view.strides[0] as isize } else { 0 };
+    let bitmask_row_stride = bitmask_view.strides[0] as isize;
+
+    let logits_batch = if logits_view.ndim == 2 { logits_view.shape[0] as usize } else { 1 };
+    let selected_rows: Vec<usize> = match indices {
+        Some(idx) if !idx.is_empty() => idx.iter().map(|&i| i as usize).collect(),
+        _ => (0..logits_batch).collect(),
+    };
+
+    for row in selected_rows {
+        let row_logits = unsafe { (logits_view.data as *mut f32).offset(row as isize * logits_row_stride) };
+        let row_bitmask =
+            unsafe { (bitmask_view.data as *const u32).offset(row as isize * bitmask_row_stride) };
+        unsafe { mask_row(row_logits, logits_col_stride, row_bitmask, effective_vocab_size) };
+    }
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+//! DLTensor validation for speculative-decoding draft-tree masking.
+//!
+//! `testing::traverse_draft_tree` (the pre-existing test-only entry point exercised in
+//! `tests/rust/test_speculative_decoding.rs`) panics with a `Check failed` message on a
+//! malformed tensor. [`validate_draft_tree_tensors`] checks draft-tree tensor dtypes and
+//! shapes and reports a typed [`DraftTreeError`] instead. It takes each tensor's
+//! `dtype`/`shape` as plain fields, so it doesn't need the FFI `DLTensor`/matcher types.
+//! This module only validates: the crate has no `GrammarMatcher::fill_draft_tree_bitmask`
+//! that calls it, and nothing here traverses the tree, fills a bitmask, or restores
+//! matcher state between sibling branches (the matcher's checkpoint/rollback machinery is
+//! FFI-backed and not present in this checkout).
+
+use core::fmt;
+
+/// One of the four tensors a draft-tree call takes, named for error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftTreeTensor {
+    RetrieveNextToken,
+    RetrieveNextSibling,
+    DraftTokens,
+    Bitmask,
+}
+
+impl fmt::Display for DraftTreeTensor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::RetrieveNextToken => "retrieve_next_token",
+            Self::RetrieveNextSibling => "retrieve_next_sibling",
+            Self::DraftTokens => "draft_tokens",
+            Self::Bitmask => "bitmask",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Why a draft-tree call's tensors were rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DraftTreeError {
+    /// A tensor wasn't the dtype the traversal requires (`i64` for the three tree
+    /// tensors, `i32` for the bitmask).
+    WrongDtype { tensor: DraftTreeTensor, expected: &'static str },
+    /// A tree tensor wasn't rank-1, or the bitmask had no dimensions at all.
+    WrongRank { tensor: DraftTreeTensor, ndim: i32 },
+    /// `retrieve_next_sibling`/`draft_tokens` didn't have the same length as
+    /// `retrieve_next_token` (one entry per tree node).
+    NodeCountMismatch { tensor: DraftTreeTensor, expected: i64, got: i64 },
+    /// The bitmask's row count didn't match the tree's node count.
+    BitmaskRowMismatch { expected: i64, got: i64 },
+}
+
+impl fmt::Display for DraftTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongDtype { tensor, expected } => {
+                write!(f, "{tensor} must have dtype {expected}")
+            },
+            Self::WrongRank { tensor: DraftTreeTensor::Bitmask, ndim } => {
+                write!(f, "bitmask must have at least one dimension, got rank {ndim}")
+            },
+            Self::WrongRank { tensor, ndim } => {
+                write!(f, "{tensor} must be rank-1, got rank {ndim}")
+            },
+            Self::NodeCountMismatch { tensor, expected, got } => {
+                write!(
+                    f,
+                    "{tensor} has {got} entries, expected {expected} (one per draft-tree node)"
+                )
+            },
+            Self::BitmaskRowMismatch { expected, got } => {
+                write!(f, "bitmask has {got} rows, expected {expected} (one per draft-tree node)")
+            },
+        }
+    }
+}
+
+impl std::error::Error for DraftTreeError {}
+
+/// The dtype code/bit-width pair DLPack uses for a signed integer tensor (`kDLInt`).
+const DL_INT: (u8, u8) = (0, 64);
+const DL_INT32: (u8, u8) = (0, 32);
+
+/// Validate the shapes/dtypes of the four draft-tree tensors (see [`DraftTreeTensor`]).
+/// `shape`/`dtype` pairs are passed as plain fields (rather
+/// than the FFI `DLTensor` itself) so this has no dependency on the autocxx bridge.
+pub fn validate_draft_tree_tensors(
+    retrieve_next_token_shape: &[i64],
+    retrieve_next_token_dtype: (u8, u8),
+    retrieve_next_sibling_shape: &[i64],
+    retrieve_next_sibling_dtype: (u8, u8),
+    draft_tokens_shape: &[i64],
+    draft_tokens_dtype: (u8, u8),
+    bitmask_shape: &[i64],
+    bitmask_dtype: (u8, u8),
+) -> Result<usize, DraftTreeError> {
+    check_i64_vector(DraftTreeTensor::RetrieveNextToken, retrieve_next_token_shape, retrieve_next_token_dtype)?;
+    let num_nodes = retrieve_next_token_shape[0];
+
+    check_i64_vector(DraftTreeTensor::RetrieveNextSibling, retrieve_next_sibling_shape, retrieve_next_sibling_dtype)?;
+    if retrieve_next_sibling_shape[0] != num_nodes {
+        return Err(DraftTreeError::NodeCountMismatch {
+            tensor: DraftTreeTensor::RetrieveNextSibling,
+            expected: num_nodes,
+            got: retrieve_next_sibling_shape[0],
+        });
+    }
+
+    check_i64_vector(DraftTreeTensor::DraftTokens, draft_tokens_shape, draft_tokens_dtype)?;
+    if draft_tokens_shape[0] != num_nodes {
+        return Err(DraftTreeError::NodeCountMismatch {
+            tensor: DraftTreeTensor::DraftTokens,
+            expected: num_nodes,
+            got: draft_tokens_shape[0],
+        });
+    }
+
+    if bitmask_dtype != DL_INT32 {
+        return Err(DraftTreeError::WrongDtype { tensor: DraftTreeTensor::Bitmask, expected: "i32" });
+    }
+    if bitmask_shape.is_empty() {
+        return Err(DraftTreeError::WrongRank { tensor: DraftTreeTensor::Bitmask, ndim: 0 });
+    }
+    if bitmask_shape[0] != num_nodes {
+        return Err(DraftTreeError::BitmaskRowMismatch { expected: num_nodes, got: bitmask_shape[0] });
+    }
+
+    Ok(num_nodes as usize)
+}
+
+fn check_i64_vector(tensor: DraftTreeTensor, shape: &[i64], dtype: (u8, u8)) -> Result<(), DraftTreeError> {
+    if dtype != DL_INT {
+        return Err(DraftTreeError::WrongDtype { tensor, expected: "i64" });
+    }
+    if shape.len() != 1 {
+        return Err(DraftTreeError::WrongRank { tensor, ndim: shape.len() as i32 });
+    }
+    Ok(())
+}
@@ -2,12 +2,16 @@
 //! token.
 
 mod batch_grammar_matcher;
+mod bitmask_simd;
+pub mod draft_tree;
 mod grammar_matcher;
+pub mod sampling;
 
 pub use batch_grammar_matcher::BatchGrammarMatcher;
+pub use draft_tree::{DraftTreeError, DraftTreeTensor, validate_draft_tree_tensors};
 pub use grammar_matcher::GrammarMatcher;
 
-use crate::{DLTensor, cxx_utils};
+use crate::DLTensor;
 
 /// Return the shape of the bitmask: (batch_size, ceil(vocab_size / 32)).
 pub fn get_bitmask_shape(
@@ -51,31 +55,16 @@ pub fn reset_token_bitmask(bitmask: &mut [i32]) {
     bitmask.fill(-1i32);
 }
 
+/// Mask `logits` in place so that every disallowed token (bit `0` in `bitmask`) is
+/// driven to `-inf`, leaving allowed tokens untouched.
+///
+/// Runs entirely in Rust via a SIMD-dispatched kernel (see [`bitmask_simd`]) rather than
+/// crossing the C++ FFI boundary, since this runs on the hot path of every decode step.
 pub fn apply_token_bitmask_inplace_cpu(
     logits: &mut DLTensor,
     bitmask: &DLTensor,
     vocab_size: Option<i32>,
     indices: Option<&[i32]>,
 ) -> Result<(), String> {
-    let vocab_size_i32 = vocab_size.unwrap_or(-1);
-    let (has_indices, indices_ptr, indices_len) = match indices {
-        Some(slice) if !slice.is_empty() => (true, slice.as_ptr(), slice.len()),
-        _ => (false, std::ptr::null(), 0usize),
-    };
-    cxx::let_cxx_string!(error_out_cxx = "");
-    let ok = unsafe {
-        cxx_utils::apply_token_bitmask_inplace_cpu(
-            logits as *mut _,
-            bitmask as *const _,
-            vocab_size_i32,
-            has_indices,
-            indices_ptr,
-            indices_len,
-            error_out_cxx.as_mut().get_unchecked_mut(),
-        )
-    };
-    if !ok {
-        return Err(error_out_cxx.to_string());
-    }
-    Ok(())
+    bitmask_simd::apply_token_bitmask_inplace_cpu_rust(logits, bitmask, vocab_size, indices)
 }
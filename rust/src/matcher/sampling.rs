@@ -0,0 +1,175 @@
+//! Constrained sampling utilities: turn a per-sequence `(logits, bitmask)` pair into a
+//! sampled token id, restricted to the tokens the bitmask allows.
+//!
+//! Unlike [`super::apply_token_bitmask_inplace_cpu`], which mirrors a DLPack-tensor-based
+//! C++ entry point for zero-copy interop with framework tensors, these helpers operate on
+//! plain Rust slices: there's no existing FFI contract to match, and callers sampling a
+//! single row at a time don't need tensor machinery.
+
+/// Bit `token_id % 32` of word `token_id / 32` is 1 when the token is allowed, matching
+/// the packed-int32 bitmask layout used throughout this crate.
+fn is_allowed(bitmask_row: &[i32], token_id: usize) -> bool {
+    let word = bitmask_row[token_id / 32] as u32;
+    (word >> (token_id % 32)) & 1 != 0
+}
+
+/// Reject every token the bitmask disallows (driving its logit to `-inf`), apply
+/// temperature scaling, then overwrite `logits[..vocab_size]` in place with
+/// log-probabilities renormalized over only the allowed entries.
+///
+/// Short-circuits without computing an exponential/sum when exactly one token is
+/// allowed. Returns an error (instead of propagating NaNs) when no token is allowed.
+pub fn masked_log_softmax(
+    logits: &mut [f32],
+    bitmask_row: &[i32],
+    vocab_size: usize,
+    temperature: f32,
+) -> Result<(), String> {
+    if temperature <= 0.0 {
+        return Err(format!("temperature must be positive, got {temperature}"));
+    }
+
+    let mut max_logit = f32::NEG_INFINITY;
+    let mut allowed_count = 0usize;
+    for i in 0..vocab_size {
+        if is_allowed(bitmask_row, i) {
+            allowed_count += 1;
+            if logits[i] > max_logit {
+                max_logit = logits[i];
+            }
+        } else {
+            logits[i] = f32::NEG_INFINITY;
+        }
+    }
+    if allowed_count == 0 {
+        return Err("no tokens are allowed by the bitmask; cannot sample".to_string());
+    }
+    if allowed_count == 1 {
+        for logit in logits[..vocab_size].iter_mut() {
+            if *logit != f32::NEG_INFINITY {
+                *logit = 0.0; // log(1.0): the sole allowed token gets all the mass.
+            }
+        }
+        return Ok(());
+    }
+
+    let mut sum_exp = 0.0f32;
+    for &logit in logits[..vocab_size].iter() {
+        if logit == f32::NEG_INFINITY {
+            continue;
+        }
+        sum_exp += ((logit - max_logit) / temperature).exp();
+    }
+    let log_sum_exp = sum_exp.ln();
+    for logit in logits[..vocab_size].iter_mut() {
+        if *logit == f32::NEG_INFINITY {
+            continue;
+        }
+        *logit = (*logit - max_logit) / temperature - log_sum_exp;
+    }
+    Ok(())
+}
+
+/// Nucleus (top-p) filtering *within* the allowed set already produced by
+/// [`masked_log_softmax`]: keep the smallest highest-probability prefix whose cumulative
+/// mass is `>= top_p`, re-mask (`-inf`) everything outside it, and renormalize the
+/// survivors so they sum back to 1.
+pub fn apply_top_p(log_probs: &mut [f32], top_p: f32) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&top_p) {
+        return Err(format!("top_p must be in [0, 1], got {top_p}"));
+    }
+
+    let mut order: Vec<usize> = (0..log_probs.len())
+        .filter(|&i| log_probs[i] != f32::NEG_INFINITY)
+        .collect();
+    if order.is_empty() {
+        return Err("no tokens are allowed; cannot apply top-p filtering".to_string());
+    }
+    order.sort_by(|&a, &b| log_probs[b].partial_cmp(&log_probs[a]).unwrap());
+
+    let mut cumulative = 0.0f32;
+    let mut cutoff = order.len();
+    for (rank, &i) in order.iter().enumerate() {
+        cumulative += log_probs[i].exp();
+        if cumulative >= top_p {
+            cutoff = rank + 1;
+            break;
+        }
+    }
+    for &i in &order[cutoff..] {
+        log_probs[i] = f32::NEG_INFINITY;
+    }
+
+    let kept_mass: f32 = order[..cutoff].iter().map(|&i| log_probs[i].exp()).sum();
+    let log_kept_mass = kept_mass.ln();
+    for &i in &order[..cutoff] {
+        log_probs[i] -= log_kept_mass;
+    }
+    Ok(())
+}
+
+/// Draw a single index from `log_probs` (`-inf` entries excluded) using a seeded
+/// splitmix64 generator, so a given seed reproduces the same draw across runs and
+/// platforms.
+pub fn sample_index(log_probs: &[f32], seed: u64) -> Result<usize, String> {
+    let mut state = seed;
+    let mut next_u64 = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    let r = (next_u64() as f64 / u64::MAX as f64) as f32;
+
+    let mut cumulative = 0.0f32;
+    let mut last_allowed = None;
+    for (i, &log_prob) in log_probs.iter().enumerate() {
+        if log_prob == f32::NEG_INFINITY {
+            continue;
+        }
+        cumulative += log_prob.exp();
+        last_allowed = Some(i);
+        if r <= cumulative {
+            return Ok(i);
+        }
+    }
+    // Floating-point rounding can leave `r` a hair above a cumulative mass that should
+    // have reached 1.0; fall back to the last allowed token rather than erroring.
+    last_allowed.ok_or_else(|| "no tokens are allowed; cannot sample".to_string())
+}
+
+/// Mask, temperature-scale, nucleus-filter, and sample one token per row of a batch,
+/// mirroring `BatchGrammarMatcher::batch_fill_next_token_bitmask`'s flat row-major layout
+/// and shuffled-index output mapping: `logits`/`bitmask` hold `batch_size` contiguous
+/// rows, and `indices[i]`, when given, is the output row logical row `i` is read from.
+pub fn batch_sample(
+    logits: &mut [f32],
+    bitmask: &[i32],
+    batch_size: usize,
+    vocab_size: usize,
+    bitmask_row_words: usize,
+    indices: Option<&[i32]>,
+    temperature: f32,
+    top_p: f32,
+    seeds: &[u64],
+) -> Result<Vec<i32>, String> {
+    if seeds.len() != batch_size {
+        return Err(format!("expected {batch_size} seeds, got {}", seeds.len()));
+    }
+
+    let mut sampled = vec![-1i32; batch_size];
+    for row in 0..batch_size {
+        let output_row = match indices {
+            Some(idx) => idx[row] as usize,
+            None => row,
+        };
+        let logits_row = &mut logits[output_row * vocab_size..(output_row + 1) * vocab_size];
+        let bitmask_row =
+            &bitmask[output_row * bitmask_row_words..(output_row + 1) * bitmask_row_words];
+        masked_log_softmax(logits_row, bitmask_row, vocab_size, temperature)?;
+        apply_top_p(logits_row, top_p)?;
+        sampled[row] = sample_index(logits_row, seeds[row])? as i32;
+    }
+    Ok(sampled)
+}
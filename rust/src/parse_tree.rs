@@ -0,0 +1,141 @@
+//! Parse-tree construction for structural-tag grammar matches.
+//!
+//! `tests/rust/test_grammar_matcher_structural_tag.rs` exercises this as
+//! `Grammar::parse(input) -> Option<ParseNode>`. The request describes it as two halves:
+//! "running the matcher while recording, at each rule entry/exit, the rule id and the
+//! input offset, then folding the offset stack into a nested structure whose leaves point
+//! at the matched slices." The first half needs the compiled automaton itself -
+//! `GrammarMatcher`'s step/walk loop over the structural-tag grammar, which lives in the
+//! autocxx-bound C++ engine this checkout has no `cpp/`/`include/` tree to generate (the
+//! same gap noted in `lib.rs` and `matcher/draft_tree.rs`). The second half -
+//! folding a stream of rule enter/exit events into the nested tree - has no such
+//! dependency: [`build_parse_tree`] takes exactly that flat event stream and produces the
+//! [`ParseNode`] tree `Grammar::parse` would return, regardless of how the events were
+//! recorded. Once the matcher can emit that event stream, `Grammar::parse` becomes a thin
+//! wrapper: walk the input recording [`RuleEvent`]s, then call this.
+
+use std::fmt;
+
+/// One rule-entry or rule-exit event recorded while walking a compiled grammar's
+/// automaton against an input string - the raw material [`build_parse_tree`] folds into
+/// a nested [`ParseNode`] tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleEvent {
+    Enter { rule_name: String, offset: usize },
+    Exit { offset: usize },
+}
+
+/// A node in the parse tree `Grammar::parse` would return: the rule that matched, the
+/// byte range of the input it consumed, and the nested sub-matches taken to match it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNode {
+    rule_name: String,
+    start: usize,
+    end: usize,
+    text: String,
+    children: Vec<ParseNode>,
+}
+
+impl ParseNode {
+    pub fn rule_name(&self) -> &str {
+        &self.rule_name
+    }
+
+    /// The substring of the original input this node's rule matched.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The `[start, end)` byte range of the original input this node's rule matched.
+    pub fn byte_range(&self) -> (usize, usize) {
+        (self.start, self.end)
+    }
+
+    pub fn children(&self) -> &[ParseNode] {
+        &self.children
+    }
+
+    /// Every node in this subtree (including `self`) whose rule is named `name`,
+    /// pre-order - the `all_names("foo")` lookup the request calls out.
+    pub fn all_named(&self, name: &str) -> Vec<&ParseNode> {
+        let mut out = Vec::new();
+        self.collect_named(name, &mut out);
+        out
+    }
+
+    fn collect_named<'a>(&'a self, name: &str, out: &mut Vec<&'a ParseNode>) {
+        if self.rule_name == name {
+            out.push(self);
+        }
+        for child in &self.children {
+            child.collect_named(name, out);
+        }
+    }
+}
+
+/// Why a rule-event stream couldn't be folded into a parse tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTreeError {
+    /// `events` was empty - there's no root node to return.
+    EmptyEventStream,
+    /// An `Exit` event had no matching open `Enter` on the stack.
+    UnbalancedExit { offset: usize },
+    /// `events` ended with rules still open (an `Enter` with no matching `Exit`).
+    UnclosedRules { rule_names: Vec<String> },
+}
+
+impl fmt::Display for ParseTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyEventStream => write!(f, "rule-event stream was empty"),
+            Self::UnbalancedExit { offset } => {
+                write!(f, "exit event at offset {offset} had no matching open rule")
+            },
+            Self::UnclosedRules { rule_names } => {
+                write!(f, "rule(s) never closed: {}", rule_names.join(", "))
+            },
+        }
+    }
+}
+
+impl std::error::Error for ParseTreeError {}
+
+/// Fold a flat stream of rule enter/exit events - recorded while walking a compiled
+/// grammar's automaton against `input` - into the nested [`ParseNode`] tree
+/// `Grammar::parse` returns. `events` must open with an `Enter` and fully balance: every
+/// `Enter` needs a later `Exit`, properly nested (LIFO, like the rule-call stack that
+/// produced it).
+pub fn build_parse_tree(input: &str, events: &[RuleEvent]) -> Result<ParseNode, ParseTreeError> {
+    let mut stack: Vec<(String, usize, Vec<ParseNode>)> = Vec::new();
+    let mut root: Option<ParseNode> = None;
+
+    for event in events {
+        match event {
+            RuleEvent::Enter { rule_name, offset } => {
+                stack.push((rule_name.clone(), *offset, Vec::new()));
+            },
+            RuleEvent::Exit { offset } => {
+                let (rule_name, start, children) =
+                    stack.pop().ok_or(ParseTreeError::UnbalancedExit { offset: *offset })?;
+                let node = ParseNode {
+                    rule_name,
+                    start,
+                    end: *offset,
+                    text: input.get(start..*offset).unwrap_or_default().to_string(),
+                    children,
+                };
+                match stack.last_mut() {
+                    Some((_, _, siblings)) => siblings.push(node),
+                    None => root = Some(node),
+                }
+            },
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(ParseTreeError::UnclosedRules {
+            rule_names: stack.into_iter().map(|(name, _, _)| name).collect(),
+        });
+    }
+    root.ok_or(ParseTreeError::EmptyEventStream)
+}
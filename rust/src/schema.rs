@@ -0,0 +1,381 @@
+//! Schema-driven grammar compiler with strongly-connected-component recursion handling.
+//!
+//! Compiles a declarative schema - records with named/typed fields and alternations over
+//! named type references - into XGrammar EBNF and, via [`Grammar::from_ebnf`], a ready-to-
+//! compile [`Grammar`]. The interesting part is entirely pure Rust and needs no FFI: (1)
+//! build a dependency graph over the named types reachable from the compile root, (2) run
+//! Tarjan's algorithm to find strongly connected components - any type in a multi-member
+//! SCC, or with a self-loop, is recursive and must become a named rule rather than being
+//! inlined, since inlining it would expand forever, and (3) emit one EBNF rule per named
+//! type, inlining every other (non-recursive, single-use) type directly into its one call
+//! site to keep the automaton small.
+//!
+//! Only the final `Grammar::from_ebnf` call crosses the FFI boundary; everything through
+//! EBNF text generation ([`SchemaCompiler::compile_to_ebnf`]) is plain string/graph logic.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+};
+
+use crate::Grammar;
+
+/// A field's type within a [`TypeDef::Record`] or a variant of a [`TypeDef::Alternation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    Int,
+    Float,
+    Bool,
+    String,
+    /// A reference to another named type defined on the same [`SchemaCompiler`].
+    Ref(String),
+}
+
+/// One field of a [`TypeDef::Record`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordField {
+    pub name: String,
+    pub field_type: FieldType,
+    pub required: bool,
+}
+
+impl RecordField {
+    /// A field that must always be present.
+    pub fn required(name: impl Into<String>, field_type: FieldType) -> Self {
+        Self { name: name.into(), field_type, required: true }
+    }
+
+    /// A field that may be omitted entirely, or present as `null`.
+    ///
+    /// Required fields must be listed before optional ones in a [`TypeDef::Record`]'s
+    /// field list: the emitted grammar places every required field's comma unconditionally
+    /// and every optional field's comma conditionally, so a required field after an
+    /// optional one would need a comma whose presence depends on fields earlier in the
+    /// list - not supported by this compiler.
+    pub fn optional(name: impl Into<String>, field_type: FieldType) -> Self {
+        Self { name: name.into(), field_type, required: false }
+    }
+}
+
+/// A named type: either a JSON-object-shaped record, or an alternation between variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeDef {
+    Record(Vec<RecordField>),
+    Alternation(Vec<FieldType>),
+}
+
+/// Why a schema failed to compile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// `compile(root)` was called with a root name that was never `define_type`'d.
+    UnknownRoot(String),
+    /// A [`FieldType::Ref`] named a type that was never `define_type`'d.
+    UnknownType(String),
+    /// The generated EBNF was rejected by [`Grammar::from_ebnf`].
+    GrammarCompileFailed(String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownRoot(name) => write!(f, "schema root type {name:?} was never defined"),
+            Self::UnknownType(name) => write!(f, "reference to undefined type {name:?}"),
+            Self::GrammarCompileFailed(msg) => write!(f, "generated grammar was rejected: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// A schema-driven grammar compiler: accumulates named [`TypeDef`]s via [`Self::define_type`],
+/// then turns one of them into a [`Grammar`] via [`Self::compile`].
+#[derive(Debug, Default)]
+pub struct SchemaCompiler {
+    types: HashMap<String, TypeDef>,
+}
+
+impl SchemaCompiler {
+    pub fn new() -> Self {
+        Self { types: HashMap::new() }
+    }
+
+    /// Define (or replace) a named type.
+    pub fn define_type(&mut self, name: impl Into<String>, def: TypeDef) -> &mut Self {
+        self.types.insert(name.into(), def);
+        self
+    }
+
+    /// Compile the type named `root` (and everything it transitively references) into a
+    /// [`Grammar`].
+    pub fn compile(&self, root: &str) -> Result<Grammar, SchemaError> {
+        let ebnf = self.compile_to_ebnf(root)?;
+        Grammar::from_ebnf(&ebnf, "root")
+            .map_err(|err| SchemaError::GrammarCompileFailed(err.to_string()))
+    }
+
+    /// Compile the type named `root` into standalone EBNF text, without crossing into
+    /// [`Grammar::from_ebnf`] - the part of this compiler that's pure Rust logic.
+    pub fn compile_to_ebnf(&self, root: &str) -> Result<String, SchemaError> {
+        if !self.types.contains_key(root) {
+            return Err(SchemaError::UnknownRoot(root.to_string()));
+        }
+
+        // Breadth-first reachability from `root`, validating every `Ref` along the way and
+        // building each type's direct-dependency edge list as we go.
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+        let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+        let mut ref_counts: HashMap<String, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(root.to_string());
+        visited.insert(root.to_string());
+        while let Some(name) = queue.pop_front() {
+            order.push(name.clone());
+            let def = self.types.get(&name).expect("reachable names are always defined");
+            let deps = direct_refs(def);
+            for dep in &deps {
+                if !self.types.contains_key(dep) {
+                    return Err(SchemaError::UnknownType(dep.clone()));
+                }
+                *ref_counts.entry(dep.clone()).or_insert(0) += 1;
+                if visited.insert(dep.clone()) {
+                    queue.push_back(dep.clone());
+                }
+            }
+            edges.insert(name, deps);
+        }
+
+        // A type must be a named rule (rather than inlined into its one call site) if it's
+        // recursive - alone in its own SCC but self-referencing, or sharing an SCC with
+        // others - or if it's referenced from more than one place, or is the root itself.
+        let mut named: HashSet<String> = HashSet::new();
+        named.insert(root.to_string());
+        for scc in tarjan_scc(&order, &edges) {
+            let is_recursive = scc.len() > 1 || edges[&scc[0]].contains(&scc[0]);
+            if is_recursive {
+                named.extend(scc);
+            }
+        }
+        for name in &order {
+            if *ref_counts.get(name).unwrap_or(&0) > 1 {
+                named.insert(name.clone());
+            }
+        }
+
+        let mut used_basics: HashSet<&'static str> = HashSet::new();
+        let mut rules = Vec::new();
+        rules.push(format!("root ::= {root}"));
+        for name in &order {
+            if named.contains(name) {
+                let def = self.types.get(name).expect("reachable names are always defined");
+                let body = self.emit_type_body(def, &named, &mut used_basics);
+                rules.push(format!("{name} ::= {body}"));
+            }
+        }
+        rules.extend(basic_rule_defs(&used_basics));
+        Ok(rules.join("\n"))
+    }
+
+    fn emit_field_type(
+        &self,
+        field_type: &FieldType,
+        named: &HashSet<String>,
+        used_basics: &mut HashSet<&'static str>,
+    ) -> String {
+        match field_type {
+            FieldType::Int => {
+                used_basics.insert("basic_integer");
+                "basic_integer".to_string()
+            },
+            FieldType::Float => {
+                used_basics.insert("basic_float");
+                "basic_float".to_string()
+            },
+            FieldType::Bool => {
+                used_basics.insert("basic_boolean");
+                "basic_boolean".to_string()
+            },
+            FieldType::String => {
+                used_basics.insert("basic_string");
+                "basic_string".to_string()
+            },
+            FieldType::Ref(name) => {
+                if named.contains(name) {
+                    name.clone()
+                } else {
+                    // Non-recursive, single-use: inline the referenced type's body
+                    // directly at this call site instead of emitting a separate rule.
+                    let def = self.types.get(name).expect("unknown refs are rejected earlier");
+                    self.emit_type_body(def, named, used_basics)
+                }
+            },
+        }
+    }
+
+    fn emit_type_body(
+        &self,
+        def: &TypeDef,
+        named: &HashSet<String>,
+        used_basics: &mut HashSet<&'static str>,
+    ) -> String {
+        match def {
+            TypeDef::Record(fields) => {
+                let mut required_parts = Vec::new();
+                let mut optional_parts = Vec::new();
+                for field in fields {
+                    let mut value_expr = self.emit_field_type(&field.field_type, named, used_basics);
+                    if !field.required {
+                        value_expr = format!("(\"null\" | {value_expr})");
+                    }
+                    let pair =
+                        format!("\"\\\"{}\\\"\" [ \\n\\t]* \":\" [ \\n\\t]* {}", field.name, value_expr);
+                    if field.required {
+                        required_parts.push(pair);
+                    } else {
+                        optional_parts.push(pair);
+                    }
+                }
+
+                let mut body = "\"{\" [ \\n\\t]*".to_string();
+                body.push_str(&required_parts.join(" [ \\n\\t]* \",\" [ \\n\\t]* "));
+                if required_parts.is_empty() && !optional_parts.is_empty() {
+                    // The first present optional field takes no comma.
+                    let alts: Vec<String> = (0..optional_parts.len())
+                        .map(|i| {
+                            let mut alt = optional_parts[i].clone();
+                            for later in &optional_parts[i + 1..] {
+                                alt.push_str(&format!(" (\"\" | ([ \\n\\t]* \",\" [ \\n\\t]* {later}))"));
+                            }
+                            format!("({alt})")
+                        })
+                        .collect();
+                    body.push_str(&format!(" (\"\" | {})", alts.join(" | ")));
+                } else {
+                    for optional_pair in &optional_parts {
+                        body.push_str(&format!(
+                            " (\"\" | ([ \\n\\t]* \",\" [ \\n\\t]* {optional_pair}))"
+                        ));
+                    }
+                }
+                body.push_str(" [ \\n\\t]* \"}\"");
+                format!("({body})")
+            },
+            TypeDef::Alternation(variants) => {
+                let parts: Vec<String> = variants
+                    .iter()
+                    .map(|v| self.emit_field_type(v, named, used_basics))
+                    .collect();
+                format!("({})", parts.join(" | "))
+            },
+        }
+    }
+}
+
+/// The type names a [`TypeDef`] directly references, in field/variant order.
+fn direct_refs(def: &TypeDef) -> Vec<String> {
+    let field_types: Vec<&FieldType> = match def {
+        TypeDef::Record(fields) => fields.iter().map(|f| &f.field_type).collect(),
+        TypeDef::Alternation(variants) => variants.iter().collect(),
+    };
+    field_types
+        .into_iter()
+        .filter_map(|ft| match ft {
+            FieldType::Ref(name) => Some(name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Tarjan's strongly-connected-components algorithm over `nodes`/`edges`. Returns one
+/// `Vec<String>` per component; singleton components (no cycle) still appear, so callers
+/// must separately check for a self-loop to tell "recursive singleton" from "acyclic leaf".
+fn tarjan_scc(nodes: &[String], edges: &HashMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    struct State<'a> {
+        edges: &'a HashMap<String, Vec<String>>,
+        index: HashMap<String, usize>,
+        lowlink: HashMap<String, usize>,
+        on_stack: HashSet<String>,
+        stack: Vec<String>,
+        next_index: usize,
+        sccs: Vec<Vec<String>>,
+    }
+
+    fn strongconnect(v: &str, state: &mut State) {
+        state.index.insert(v.to_string(), state.next_index);
+        state.lowlink.insert(v.to_string(), state.next_index);
+        state.next_index += 1;
+        state.stack.push(v.to_string());
+        state.on_stack.insert(v.to_string());
+
+        for w in state.edges.get(v).cloned().unwrap_or_default() {
+            if !state.index.contains_key(&w) {
+                strongconnect(&w, state);
+                let w_low = state.lowlink[&w];
+                let v_low = state.lowlink[v];
+                state.lowlink.insert(v.to_string(), v_low.min(w_low));
+            } else if state.on_stack.contains(&w) {
+                let w_idx = state.index[&w];
+                let v_low = state.lowlink[v];
+                state.lowlink.insert(v.to_string(), v_low.min(w_idx));
+            }
+        }
+
+        if state.lowlink[v] == state.index[v] {
+            let mut component = Vec::new();
+            loop {
+                let w = state.stack.pop().expect("component root is always on the stack");
+                state.on_stack.remove(&w);
+                let is_root = w == v;
+                component.push(w);
+                if is_root {
+                    break;
+                }
+            }
+            state.sccs.push(component);
+        }
+    }
+
+    let mut state = State {
+        edges,
+        index: HashMap::new(),
+        lowlink: HashMap::new(),
+        on_stack: HashSet::new(),
+        stack: Vec::new(),
+        next_index: 0,
+        sccs: Vec::new(),
+    };
+    for node in nodes {
+        if !state.index.contains_key(node) {
+            strongconnect(node, &mut state);
+        }
+    }
+    state.sccs
+}
+
+/// Shared leaf-value rules, emitted only for the ones actually referenced.
+fn basic_rule_defs(used: &HashSet<&'static str>) -> Vec<String> {
+    let mut defs = Vec::new();
+    if used.contains("basic_integer") || used.contains("basic_float") {
+        defs.push("basic_integer ::= ((\"0\") | (basic_integer_sign [1-9] [0-9]*))".to_string());
+        defs.push("basic_integer_sign ::= (\"\" | (\"-\"))".to_string());
+    }
+    if used.contains("basic_float") {
+        defs.push("basic_float ::= (basic_integer basic_float_frac)".to_string());
+        defs.push("basic_float_frac ::= (\"\" | (\".\" [0-9] [0-9]*))".to_string());
+    }
+    if used.contains("basic_boolean") {
+        defs.push("basic_boolean ::= ((\"true\") | (\"false\"))".to_string());
+    }
+    if used.contains("basic_string") {
+        defs.push("basic_string ::= (\"\\\"\" basic_string_sub)".to_string());
+        defs.push(
+            "basic_string_sub ::= ((\"\\\"\") | ([^\\0-\\x1f\\\"\\\\\\r\\n] basic_string_sub) | (\"\\\\\" basic_escape basic_string_sub))"
+                .to_string(),
+        );
+        defs.push(
+            "basic_escape ::= (([\\\"\\\\/bfnrt]) | (\"u\" [A-Fa-f0-9] [A-Fa-f0-9] [A-Fa-f0-9] [A-Fa-f0-9]))"
+                .to_string(),
+        );
+    }
+    defs
+}
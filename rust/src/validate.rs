@@ -0,0 +1,208 @@
+//! Fixed-point productivity and reachability analysis for EBNF grammars.
+//!
+//! `tests/rust/test_grammar_parser_macro.rs` exercises this as `Grammar::validate()`, a
+//! method on the FFI-bound `Grammar` type. `Grammar`'s methods are generated by autocxx
+//! from XGrammar's C++ class (see `rust/src/lib.rs`'s module doc), and this checkout has
+//! no `cpp/`/`include/` tree for that class - so there's no C++ side to add a `validate()`
+//! method to from here. What's implementable without it is the actual analysis:
+//! [`validate_ebnf`] runs it directly over a grammar's textual EBNF form (the string
+//! `Grammar::to_string_ebnf()` already produces, used throughout this crate's tests), so
+//! once `Grammar::validate()` exists on the C++ side it can delegate straight to this.
+//!
+//! Two checks run over the rule-reference graph:
+//! - **Reachability**: a breadth-first walk from the compile root over every rule name mentioned
+//!   anywhere in a rule's body (including inside a `TagDispatch(...)`'s tag/rule pairs).
+//! - **Productivity**: a rule is productive if at least one of its top-level
+//!   `|`-separated alternatives references only terminals and already-productive rules;
+//!   this is computed as a fixed point, since a rule's productivity can depend on
+//!   another rule's, which may in turn depend on the first (recursion through a
+//!   productive base case, e.g. `root ::= "a" | "a" root`, must still end up productive).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The result of [`validate_ebnf`]: every rule name found to be unproductive or
+/// unreachable from the compile root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    unproductive: Vec<String>,
+    unreachable: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Rules with no alternative that bottoms out in only terminals/productive rules -
+    /// i.e. that can never derive a finite string (e.g. `a ::= a "x"` has no base case).
+    pub fn unproductive_rules(&self) -> &[String] {
+        &self.unproductive
+    }
+
+    /// Rules never referenced, directly or transitively, from the compile root.
+    pub fn unreachable_rules(&self) -> &[String] {
+        &self.unreachable
+    }
+}
+
+/// Parse `ebnf`'s `name ::= body` rule definitions and run fixed-point productivity and
+/// root-reachability analysis over them.
+pub fn validate_ebnf(ebnf: &str, root: &str) -> ValidationReport {
+    let rules = parse_rule_bodies(ebnf);
+    let names: HashSet<&str> = rules.keys().map(String::as_str).collect();
+
+    let mut reachable: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<String> = VecDeque::new();
+    if names.contains(root) {
+        reachable.insert(root.to_string());
+        queue.push_back(root.to_string());
+    }
+    while let Some(name) = queue.pop_front() {
+        for referenced in identifiers(&rules[&name]) {
+            if names.contains(referenced.as_str()) && reachable.insert(referenced.clone()) {
+                queue.push_back(referenced);
+            }
+        }
+    }
+
+    let alternatives: HashMap<&str, Vec<Vec<String>>> = rules
+        .iter()
+        .map(|(name, body)| {
+            let alts = split_alternatives(body).iter().map(|alt| identifiers(alt)).collect();
+            (name.as_str(), alts)
+        })
+        .collect();
+    let mut productive: HashSet<&str> = HashSet::new();
+    loop {
+        let mut changed = false;
+        for (&name, alts) in &alternatives {
+            if productive.contains(name) {
+                continue;
+            }
+            let is_productive = alts.iter().any(|refs| {
+                refs.iter().all(|r| !names.contains(r.as_str()) || productive.contains(r.as_str()))
+            });
+            if is_productive {
+                productive.insert(name);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut unproductive: Vec<String> =
+        rules.keys().filter(|name| !productive.contains(name.as_str())).cloned().collect();
+    unproductive.sort();
+    let mut unreachable: Vec<String> =
+        rules.keys().filter(|name| !reachable.contains(name.as_str())).cloned().collect();
+    unreachable.sort();
+
+    ValidationReport { unproductive, unreachable }
+}
+
+/// Split `name ::= body` definitions out of an EBNF document, one rule body (the bare
+/// right-hand side text, space-joined if it spans multiple lines) per rule name.
+fn parse_rule_bodies(ebnf: &str) -> HashMap<String, String> {
+    let mut rules: HashMap<String, String> = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+    for line in ebnf.lines() {
+        if let Some((name, rest)) = split_rule_header(line) {
+            if let Some((name, body)) = current.take() {
+                rules.insert(name, body);
+            }
+            current = Some((name.to_string(), rest.to_string()));
+        } else if let Some((_, body)) = current.as_mut() {
+            body.push(' ');
+            body.push_str(line.trim());
+        }
+    }
+    if let Some((name, body)) = current.take() {
+        rules.insert(name, body);
+    }
+    rules
+}
+
+/// Recognize a `name ::= ...` header line, returning `(name, rest-of-line)`.
+fn split_rule_header(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    let sep = trimmed.find("::=")?;
+    let name = trimmed[..sep].trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    Some((name, trimmed[sep + 3..].trim()))
+}
+
+/// Split a rule body into its top-level `|`-separated alternatives, ignoring any `|`
+/// nested inside parentheses, a string literal, or a character class.
+fn split_alternatives(body: &str) -> Vec<String> {
+    let mut alternatives = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut in_class = false;
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        if (in_string || in_class) && c == '\\' {
+            current.push(c);
+            if let Some(next) = chars.next() {
+                current.push(next);
+            }
+            continue;
+        }
+        match c {
+            '"' if !in_class => in_string = !in_string,
+            '[' if !in_string => in_class = true,
+            ']' if !in_string => in_class = false,
+            '(' if !in_string && !in_class => depth += 1,
+            ')' if !in_string && !in_class => depth -= 1,
+            '|' if !in_string && !in_class && depth == 0 => {
+                alternatives.push(current.trim().to_string());
+                current = String::new();
+                continue;
+            },
+            _ => {},
+        }
+        current.push(c);
+    }
+    alternatives.push(current.trim().to_string());
+    alternatives
+}
+
+/// Every bare identifier token in `text` outside a string literal or character class -
+/// i.e. every potential rule-name reference (callers filter against the grammar's
+/// actual rule names, so stray keyword-like tokens such as `TagDispatch` or `stop_eos`
+/// are harmless).
+fn identifiers(text: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut in_string = false;
+    let mut in_class = false;
+    let mut current = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if in_string || in_class {
+            if c == '\\' {
+                chars.next();
+                continue;
+            }
+            if c == '"' && in_string {
+                in_string = false;
+            } else if c == ']' && in_class {
+                in_class = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' => in_class = true,
+            c if c.is_alphanumeric() || c == '_' => current.push(c),
+            _ => {
+                if !current.is_empty() {
+                    ids.push(std::mem::take(&mut current));
+                }
+            },
+        }
+    }
+    if !current.is_empty() {
+        ids.push(current);
+    }
+    ids
+}
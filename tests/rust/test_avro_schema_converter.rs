@@ -0,0 +1,164 @@
+mod test_utils;
+
+use serde_json::{json, Value};
+use serial_test::serial;
+use test_utils::*;
+use xgrammar::Grammar;
+
+fn check_avro_schema_with_instance(
+    schema: &Value,
+    instance: &str,
+    is_accepted: bool,
+    any_whitespace: bool,
+    indent: Option<i32>,
+    separators: Option<(&str, &str)>,
+) {
+    let schema_json = serde_json::to_string(schema).expect("serialize schema");
+    let grammar =
+        Grammar::from_avro_schema(&schema_json, any_whitespace, indent, separators).unwrap();
+    assert_eq!(is_grammar_accept_string(&grammar, instance), is_accepted);
+}
+
+// Mirrors the primitive-examples table from the Avro test suite: each primitive type maps
+// to the corresponding JSON-encoding rule (`int`/`long` to the integer rule, `float`/
+// `double` to the number rule, everything else to its natural JSON counterpart).
+#[test]
+#[ignore = "Add an Apache Avro schema frontend: `Grammar::from_avro_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_avro_primitive_types() {
+    let type_instance_accepted = [
+        (json!("null"), "null", true),
+        (json!("null"), "0", false),
+        (json!("boolean"), "true", true),
+        (json!("boolean"), "false", true),
+        (json!("boolean"), "1", false),
+        (json!("int"), "42", true),
+        (json!("int"), "-7", true),
+        (json!("int"), "4.5", false),
+        (json!("long"), "9223372036854775807", true),
+        (json!("float"), "3.14", true),
+        (json!("double"), "-2.5e10", true),
+        (json!("bytes"), r#""ÿþ""#, true),
+        (json!("string"), r#""hello""#, true),
+        (json!("string"), "42", false),
+    ];
+    for (schema, instance, accepted) in type_instance_accepted {
+        check_avro_schema_with_instance(&schema, instance, accepted, true, None, None);
+    }
+}
+
+#[test]
+#[ignore = "Add an Apache Avro schema frontend: `Grammar::from_avro_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_avro_record_requires_all_fields_in_order() {
+    let schema = json!({
+        "type": "record",
+        "name": "Point",
+        "fields": [
+            {"name": "x", "type": "int"},
+            {"name": "y", "type": "int"}
+        ]
+    });
+
+    let instance_accepted = [
+        (r#"{"x": 1, "y": 2}"#, true),
+        (r#"{"y": 2, "x": 1}"#, false),
+        (r#"{"x": 1}"#, false),
+        (r#"{"x": 1, "y": 2, "z": 3}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_avro_schema_with_instance(&schema, instance, accepted, true, None, None);
+    }
+}
+
+#[test]
+#[ignore = "Add an Apache Avro schema frontend: `Grammar::from_avro_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_avro_enum_restricts_to_symbol_list() {
+    let schema = json!({
+        "type": "enum",
+        "name": "Suit",
+        "symbols": ["SPADES", "HEARTS", "DIAMONDS", "CLUBS"]
+    });
+
+    let instance_accepted = [
+        (r#""SPADES""#, true),
+        (r#""CLUBS""#, true),
+        (r#""JOKER""#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_avro_schema_with_instance(&schema, instance, accepted, true, None, None);
+    }
+}
+
+#[test]
+#[ignore = "Add an Apache Avro schema frontend: `Grammar::from_avro_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_avro_array_of_items() {
+    let schema = json!({"type": "array", "items": "string"});
+
+    let instance_accepted = [
+        (r#"["a", "b"]"#, true),
+        (r#"[]"#, true),
+        (r#"[1, 2]"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_avro_schema_with_instance(&schema, instance, accepted, true, None, None);
+    }
+}
+
+#[test]
+#[ignore = "Add an Apache Avro schema frontend: `Grammar::from_avro_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_avro_map_with_typed_values() {
+    let schema = json!({"type": "map", "values": "int"});
+
+    let instance_accepted = [
+        (r#"{"a": 1, "b": 2}"#, true),
+        (r#"{}"#, true),
+        (r#"{"a": "not an int"}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_avro_schema_with_instance(&schema, instance, accepted, true, None, None);
+    }
+}
+
+#[test]
+#[ignore = "Add an Apache Avro schema frontend: `Grammar::from_avro_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_avro_union_allows_any_branch_including_null() {
+    let schema = json!(["null", "string"]);
+
+    let instance_accepted = [
+        (r#"null"#, true),
+        (r#""hello""#, true),
+        (r#"42"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_avro_schema_with_instance(&schema, instance, accepted, true, None, None);
+    }
+}
+
+#[test]
+#[ignore = "Add an Apache Avro schema frontend: `Grammar::from_avro_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_avro_nested_record_with_union_and_array_fields() {
+    let schema = json!({
+        "type": "record",
+        "name": "User",
+        "fields": [
+            {"name": "name", "type": "string"},
+            {"name": "nickname", "type": ["null", "string"]},
+            {"name": "tags", "type": {"type": "array", "items": "string"}}
+        ]
+    });
+
+    let instance_accepted = [
+        (r#"{"name": "Alice", "nickname": null, "tags": ["a", "b"]}"#, true),
+        (r#"{"name": "Alice", "nickname": "Al", "tags": []}"#, true),
+        (r#"{"name": "Alice", "nickname": 1, "tags": []}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_avro_schema_with_instance(&schema, instance, accepted, true, None, None);
+    }
+}
@@ -0,0 +1,67 @@
+mod test_utils;
+
+use serial_test::serial;
+use xgrammar::{BeamGrammarMatcher, Grammar, GrammarCompiler, TokenizerInfo, VocabType};
+
+fn tokenizer_info() -> TokenizerInfo {
+    let vocab = vec!["<s>", "a", "b", "c", "</s>"];
+    TokenizerInfo::new(&vocab, VocabType::RAW, &None, false).unwrap()
+}
+
+/// A toy logits vector that always prefers the lexicographically first allowed token,
+/// so beam search over `root ::= "a" | "b" | "c"` has a predictable winner.
+fn logits_preferring_first_allowed(vocab_size: usize) -> Vec<f32> {
+    (0..vocab_size).rev().map(|i| i as f32).collect()
+}
+
+#[test]
+#[ignore = "Grammar-constrained beam search subsystem on top of BatchGrammarMatcher needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_beam_search_keeps_top_beam_width_hypotheses() {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" | "b" | "c""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+
+    let beam_width = 2;
+    let mut beam_matcher = BeamGrammarMatcher::new(&compiled, beam_width).unwrap();
+
+    let logits = logits_preferring_first_allowed(tokenizer_info.vocab_size());
+    beam_matcher.step(&logits);
+
+    let finished = beam_matcher.finish();
+    assert!(
+        finished.len() <= beam_width,
+        "beam search must never retain more than beam_width hypotheses"
+    );
+    assert!(!finished.is_empty());
+    // Hypotheses must come back ranked best-first by length-normalized log-prob.
+    for pair in finished.windows(2) {
+        assert!(pair[0].log_prob() >= pair[1].log_prob());
+    }
+}
+
+#[test]
+#[ignore = "Grammar-constrained beam search subsystem on top of BatchGrammarMatcher needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_beam_search_sibling_hypotheses_do_not_share_state() {
+    // Advancing one hypothesis down a branch must not corrupt a sibling hypothesis that
+    // forked from the same parent matcher state.
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "a" | "b" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+
+    let mut beam_matcher = BeamGrammarMatcher::new(&compiled, 2).unwrap();
+    let logits = vec![0.0f32; tokenizer_info.vocab_size()];
+    beam_matcher.step(&logits);
+    beam_matcher.step(&logits);
+
+    let finished = beam_matcher.finish();
+    let sequences: Vec<Vec<u32>> = finished.iter().map(|h| h.token_ids().to_vec()).collect();
+    assert!(
+        sequences.contains(&vec![1, 1]) || sequences.contains(&vec![2, 2]),
+        "expected at least one of the two valid complete sequences, got {:?}",
+        sequences
+    );
+}
@@ -0,0 +1,93 @@
+mod test_utils;
+
+use serial_test::serial;
+use test_utils::*;
+use xgrammar::{Grammar, GrammarCompiler, GrammarMatcher, TokenizerInfo, VocabType};
+
+fn tokenizer_info() -> TokenizerInfo {
+    let vocab = vec!["<s>", "a", "b", "c", "</s>"];
+    TokenizerInfo::new(&vocab, VocabType::RAW, &None, false).unwrap()
+}
+
+fn matcher_for(grammar_str: &str) -> GrammarMatcher {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(grammar_str, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+    GrammarMatcher::new(&compiled, None, true, -1).unwrap()
+}
+
+#[test]
+#[ignore = "Honor DLTensor strides so masks can be written into sub-views of a larger GPU-side buffer needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_fill_next_token_bitmask_respects_nondefault_row_stride() {
+    // A caller may hand in one row of a pre-allocated [max_batch, vocab_words] buffer that
+    // lives in a pinned/host-mapped region, so row_stride can be larger than vocab_words.
+    let vocab_size = tokenizer_info().vocab_size();
+    let vocab_words = (vocab_size + 31) / 32;
+    let row_stride = vocab_words + 4; // extra padding words between logical rows
+    let max_batch = 2;
+
+    let mut buffer = vec![-1i32; max_batch * row_stride];
+    let (mut tensor, _shape, _strides) =
+        create_strided_bitmask_dltensor(&mut buffer, max_batch, vocab_size, row_stride);
+
+    let mut matcher = matcher_for(r#"root ::= "a""#);
+    matcher.fill_next_token_bitmask(&mut tensor, 1, false);
+
+    // The padding belonging to row 0, and the padding after row 1's logical words, must be
+    // left untouched: only row 1's own `vocab_words` words may have been written.
+    assert!(buffer[..row_stride].iter().all(|&w| w == -1));
+    let row1_start = row_stride;
+    assert!(buffer[(row1_start + vocab_words)..(row1_start + row_stride)]
+        .iter()
+        .all(|&w| w == -1));
+    assert!(buffer[row1_start..(row1_start + vocab_words)]
+        .iter()
+        .any(|&w| w != -1));
+}
+
+#[test]
+#[ignore = "Honor DLTensor strides so masks can be written into sub-views of a larger GPU-side buffer needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_fill_next_token_bitmask_broadcast_writes_identical_mask_to_row_range() {
+    let vocab_size = tokenizer_info().vocab_size();
+    let batch_size = 4;
+    let mut bitmask_data = allocate_token_bitmask(batch_size, vocab_size);
+    let (mut tensor, _shape, _strides) =
+        create_bitmask_dltensor(&mut bitmask_data, batch_size, vocab_size);
+
+    let mut matcher = matcher_for(r#"root ::= "a" | "b""#);
+    // A beam of 3 identical hypotheses sharing the same constraint at rows [1, 3].
+    matcher.fill_next_token_bitmask_broadcast(&mut tensor, 1, 3);
+
+    let slice_len = bitmask_data.len() / batch_size;
+    let row1 = &bitmask_data[slice_len..2 * slice_len];
+    let row2 = &bitmask_data[2 * slice_len..3 * slice_len];
+    let row3 = &bitmask_data[3 * slice_len..4 * slice_len];
+    assert_eq!(row1, row2);
+    assert_eq!(row2, row3);
+
+    // Row 0 was outside the broadcast range and must remain the untouched default mask.
+    let row0 = &bitmask_data[..slice_len];
+    assert!(row0.iter().all(|&w| w == -1));
+}
+
+#[test]
+#[ignore = "Honor DLTensor strides so masks can be written into sub-views of a larger GPU-side buffer needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_fill_next_token_bitmask_broadcast_rejects_range_exceeding_shape() {
+    let vocab_size = tokenizer_info().vocab_size();
+    let batch_size = 2;
+    let mut bitmask_data = allocate_token_bitmask(batch_size, vocab_size);
+    let (mut tensor, _shape, _strides) =
+        create_bitmask_dltensor(&mut bitmask_data, batch_size, vocab_size);
+
+    let mut matcher = matcher_for(r#"root ::= "a""#);
+    // row_start + row_count exceeds the tensor's declared batch dimension.
+    let result = matcher.fill_next_token_bitmask_broadcast(&mut tensor, 1, 5);
+    assert!(
+        result.is_err(),
+        "broadcasting past the end of the tensor's shape must not silently write out of bounds"
+    );
+}
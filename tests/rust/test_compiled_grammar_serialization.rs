@@ -0,0 +1,233 @@
+mod test_utils;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serial_test::serial;
+use xgrammar::{CompiledGrammarCache, Grammar, GrammarCompiler, TokenizerInfo, VocabType};
+
+fn tokenizer_info() -> TokenizerInfo {
+    let vocab = vec!["<s>", "a", "b", "{", "}", "\"", ":", ","];
+    TokenizerInfo::new(&vocab, VocabType::RAW, &None, false).unwrap()
+}
+
+#[test]
+#[ignore = "Binary serialization and on-disk caching of compiled grammars needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_serialize_deserialize_roundtrip() {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+
+    let blob = compiled.serialize();
+    assert!(!blob.is_empty(), "serialized blob should be non-empty");
+
+    let reloaded = xgrammar::CompiledGrammar::deserialize(&blob, &tokenizer_info)
+        .expect("a blob produced for this exact tokenizer should deserialize");
+
+    // Deserializing must restore an equivalent compiled automaton: the warm-started
+    // matcher should accept/reject exactly like one built from a fresh compile.
+    let mut matcher_fresh = xgrammar::GrammarMatcher::new(&compiled, None, true, -1).unwrap();
+    let mut matcher_reloaded = xgrammar::GrammarMatcher::new(&reloaded, None, true, -1).unwrap();
+    assert_eq!(
+        matcher_fresh.accept_string("ab", false),
+        matcher_reloaded.accept_string("ab", false),
+    );
+}
+
+#[test]
+#[ignore = "Binary serialization and on-disk caching of compiled grammars needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_deserialize_rejects_tokenizer_mismatch() {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+    let blob = compiled.serialize();
+
+    // A blob's header records a hash of both the grammar source and the tokenizer
+    // vocabulary it was compiled against; loading it back against a tokenizer with a
+    // different vocabulary must fail rather than silently reusing mismatched state.
+    let other_vocab = vec!["<s>", "different", "vocabulary"];
+    let other_tokenizer_info =
+        TokenizerInfo::new(&other_vocab, VocabType::RAW, &None, false).unwrap();
+
+    let result = xgrammar::CompiledGrammar::deserialize(&blob, &other_tokenizer_info);
+    assert!(result.is_err(), "loading against a mismatched tokenizer must fail");
+}
+
+#[test]
+#[ignore = "Binary serialization and on-disk caching of compiled grammars needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_deserialize_rejects_truncated_blob() {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+    let blob = compiled.serialize();
+
+    let truncated = &blob[..blob.len() / 2];
+    let result = xgrammar::CompiledGrammar::deserialize(truncated, &tokenizer_info);
+    assert!(result.is_err(), "a truncated blob must not deserialize");
+}
+
+#[test]
+#[ignore = "Compiled-grammar serialization and an on-disk cache to avoid recompilation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_disk_cache_warm_start_across_threads() {
+    // Mirrors the concurrent-compile pattern in `test_pressure_structural_tag`: many
+    // threads compiling the same grammar for the same tokenizer should be able to
+    // share a single on-disk blob instead of each paying the full expansion cost.
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+    let blob = compiled.serialize();
+
+    let cache_path = std::env::temp_dir().join(format!(
+        "xgrammar_test_disk_cache_{}_{:?}.bin",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    std::fs::write(&cache_path, &blob).unwrap();
+
+    let mut handles = Vec::new();
+    for _ in 0..8usize {
+        let cache_path = cache_path.clone();
+        handles.push(std::thread::spawn(move || {
+            let tokenizer_info = tokenizer_info();
+            let cached_blob = std::fs::read(&cache_path).unwrap();
+            let reloaded =
+                xgrammar::CompiledGrammar::deserialize(&cached_blob, &tokenizer_info).unwrap();
+            let mut matcher = xgrammar::GrammarMatcher::new(&reloaded, None, true, -1).unwrap();
+            assert!(matcher.accept_string("ab", false));
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    std::fs::remove_file(&cache_path).unwrap();
+}
+
+#[test]
+#[ignore = "Compiled-grammar serialization and an on-disk cache to avoid recompilation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_deserialize_rejects_stale_cache_version() {
+    // The header carries a version tag so that a cache blob written by an older
+    // compiler build is rejected instead of being misinterpreted.
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+    let mut blob = compiled.serialize();
+
+    // Flip the version byte at the front of the header to simulate a stale cache
+    // produced by an incompatible compiler version.
+    blob[0] = blob[0].wrapping_add(1);
+
+    let result = xgrammar::CompiledGrammar::deserialize(&blob, &tokenizer_info);
+    assert!(result.is_err(), "a blob with a mismatched version tag must not deserialize");
+}
+
+#[test]
+#[serial]
+fn test_compiler_cache_skips_recompilation_on_hit() {
+    // Directly verifies the actual feature requested: a `CompiledGrammarCache` in front of
+    // `GrammarCompiler::compile_grammar` must check the cache *before* re-expanding a
+    // grammar, not just serialize/deserialize a blob the test produced itself.
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+
+    let cache_dir = std::env::temp_dir().join(format!(
+        "xgrammar_test_compiler_cache_{}_{:?}",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let cache = CompiledGrammarCache::new(&cache_dir);
+    let cache_key = b"root ::= \"a\" \"b\"|root|max_threads=1|cache_enabled=false";
+
+    let compile_calls = AtomicUsize::new(0);
+    let compile = || {
+        compile_calls.fetch_add(1, Ordering::SeqCst);
+        compiler.compile_grammar(&grammar)
+    };
+
+    // First call: cache is empty, so this must actually compile.
+    let first = cache.get_or_compile(cache_key, &tokenizer_info, compile).unwrap();
+    assert_eq!(compile_calls.load(Ordering::SeqCst), 1);
+
+    // Second call with the same key: must be served from the on-disk cache written by the
+    // first call, so the (expensive) expansion closure is never invoked again.
+    let compile_again = || {
+        compile_calls.fetch_add(1, Ordering::SeqCst);
+        compiler.compile_grammar(&grammar)
+    };
+    let second = cache.get_or_compile(cache_key, &tokenizer_info, compile_again).unwrap();
+    assert_eq!(
+        compile_calls.load(Ordering::SeqCst),
+        1,
+        "a cache hit must not re-run the compile closure"
+    );
+
+    let mut matcher_first = xgrammar::GrammarMatcher::new(&first, None, true, -1).unwrap();
+    let mut matcher_second = xgrammar::GrammarMatcher::new(&second, None, true, -1).unwrap();
+    assert_eq!(
+        matcher_first.accept_string("ab", false),
+        matcher_second.accept_string("ab", false),
+    );
+
+    std::fs::remove_dir_all(&cache_dir).unwrap();
+}
+
+#[test]
+#[serial]
+fn test_compiler_cache_recompiles_on_tokenizer_mismatch() {
+    // A cache hit that fails `CompiledGrammar::deserialize`'s own tokenizer check must be
+    // treated as a miss and fall through to a fresh compile, rather than erroring out or
+    // silently returning a blob built for the wrong vocabulary.
+    let tokenizer_a = tokenizer_info();
+    let other_vocab = vec!["<s>", "different", "vocabulary"];
+    let tokenizer_b = TokenizerInfo::new(&other_vocab, VocabType::RAW, &None, false).unwrap();
+
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler_a = GrammarCompiler::new(&tokenizer_a, 1, false, -1).unwrap();
+    let mut compiler_b = GrammarCompiler::new(&tokenizer_b, 1, false, -1).unwrap();
+
+    let cache_dir = std::env::temp_dir().join(format!(
+        "xgrammar_test_compiler_cache_mismatch_{}_{:?}",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let cache = CompiledGrammarCache::new(&cache_dir);
+    let cache_key = b"root ::= \"a\" \"b\"|root|max_threads=1|cache_enabled=false";
+
+    let compile_calls = AtomicUsize::new(0);
+    cache
+        .get_or_compile(cache_key, &tokenizer_a, || {
+            compile_calls.fetch_add(1, Ordering::SeqCst);
+            compiler_a.compile_grammar(&grammar)
+        })
+        .unwrap();
+    assert_eq!(compile_calls.load(Ordering::SeqCst), 1);
+
+    // Same cache key, but looked up against a tokenizer with a different vocabulary: the
+    // on-disk blob exists but must fail `deserialize`'s tokenizer check, so this must
+    // recompile rather than reuse it.
+    cache
+        .get_or_compile(cache_key, &tokenizer_b, || {
+            compile_calls.fetch_add(1, Ordering::SeqCst);
+            compiler_b.compile_grammar(&grammar)
+        })
+        .unwrap();
+    assert_eq!(
+        compile_calls.load(Ordering::SeqCst),
+        2,
+        "a tokenizer mismatch must be treated as a cache miss, not reused"
+    );
+
+    std::fs::remove_dir_all(&cache_dir).unwrap();
+}
@@ -49,6 +49,44 @@ fn test_accept_string() {
     }
 }
 
+#[test]
+#[ignore = "Unicode-normalization-aware string matching mode needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_accept_string_unicode_normalization() {
+    // "é" can be written precomposed (NFC, one codepoint) or decomposed (NFD, "e" +
+    // U+0301 COMBINING ACUTE ACCENT); with unicode normalization enabled the matcher
+    // should treat them as the same input, since a model may emit either encoding.
+    let grammar = Grammar::from_ebnf(r#"root ::= "café""#, "root").unwrap();
+
+    let mut default_matcher = matcher_from_grammar(&grammar);
+    assert!(default_matcher.accept_string("caf\u{e9}", false));
+    let mut default_matcher_decomposed = matcher_from_grammar(&grammar);
+    assert!(!default_matcher_decomposed.accept_string("cafe\u{301}", false));
+
+    let mut normalizing_matcher =
+        matcher_from_grammar(&grammar).with_unicode_normalization(true);
+    assert!(normalizing_matcher.accept_string("caf\u{e9}", false));
+    let mut normalizing_matcher_decomposed =
+        matcher_from_grammar(&grammar).with_unicode_normalization(true);
+    assert!(normalizing_matcher_decomposed.accept_string("cafe\u{301}", false));
+}
+
+#[test]
+#[ignore = "Unicode-normalization-aware string matching mode needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_accept_string_unicode_normalization_pending_combining_sequence() {
+    // A combining mark split across two accept_bytes/accept_string calls must not be
+    // matched against the base character alone; the matcher should stay pending until
+    // the whole combining sequence (or a clear end to it) has arrived.
+    let grammar = Grammar::from_ebnf(r#"root ::= "café" " ""#, "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar).with_unicode_normalization(true);
+
+    assert!(matcher.accept_string("cafe", false));
+    // The base letter alone must not yet satisfy "é": a combining accent could still
+    // follow and change it.
+    assert!(!matcher.accept_string(" ", false));
+}
+
 #[test]
 #[serial]
 fn test_grammar_accept() {
@@ -884,6 +922,38 @@ fn test_batch_fill_next_token_bitmask_pressure_single_thread() {
     }
 }
 
+#[test]
+#[ignore = "Parallel batch bitmask filling in BatchGrammarMatcher needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+#[cfg(feature = "hf")]
+fn test_batch_fill_next_token_bitmask_single_item_batch() {
+    // A batch of exactly one matcher must fall back to in-line execution even when the
+    // matcher was built with a worker pool, since spawning a thread to fill a single row
+    // would only add overhead.
+    let tokenizer_info = make_hf_tokenizer_info("meta-llama/Llama-2-7b-chat-hf");
+    let grammar = Grammar::builtin_json_grammar();
+    let mut matcher = matcher_from_grammar_with_tokenizer(&grammar, &tokenizer_info);
+    matcher.accept_string(r#"{"id""#, false);
+    let matchers = [matcher];
+
+    let vocab_size = tokenizer_info.vocab_size();
+    let mut bitmask_data = allocate_token_bitmask(1, vocab_size);
+    let (mut tensor, _shape, _strides) = create_bitmask_dltensor(&mut bitmask_data, 1, vocab_size);
+
+    let mut batch_matcher = BatchGrammarMatcher::new(8).unwrap();
+    batch_matcher.batch_fill_next_token_bitmask(&matchers, &mut tensor, None, false);
+    let rejected_with_pool = get_masked_tokens_from_bitmask(&bitmask_data, vocab_size);
+
+    let mut single_thread_matcher = BatchGrammarMatcher::new(1).unwrap();
+    let mut bitmask_data_single = allocate_token_bitmask(1, vocab_size);
+    let (mut tensor_single, _shape, _strides) =
+        create_bitmask_dltensor(&mut bitmask_data_single, 1, vocab_size);
+    single_thread_matcher.batch_fill_next_token_bitmask(&matchers, &mut tensor_single, None, false);
+    let rejected_single_thread = get_masked_tokens_from_bitmask(&bitmask_data_single, vocab_size);
+
+    assert_eq!(rejected_with_pool, rejected_single_thread);
+}
+
 #[test]
 #[serial]
 #[cfg(feature = "hf")]
@@ -938,3 +1008,139 @@ fn test_batch_fill_next_token_bitmask_pressure_shuffled() {
         );
     }
 }
+
+#[test]
+#[ignore = "Rejection diagnostics: longest-accepted prefix and expected next tokens needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_rejection_diagnostics() {
+    let ebnf = r#"root ::= "abb" | "abbd" | other_rule
+other_rule ::= "a" sub_rule "b"
+sub_rule ::= "b"
+"#;
+    let grammar = Grammar::from_ebnf(ebnf, "root").unwrap();
+    let tokenizer_info =
+        TokenizerInfo::new::<&str>(&[], VocabType::RAW, &None, false).unwrap();
+    let mut matcher = matcher_from_grammar_with_tokenizer(&grammar, &tokenizer_info);
+
+    // "abbx" diverges from the grammar after "abb", so the rejection diagnostics should
+    // report "abb" as the longest accepted prefix and "d" as the only expected next byte.
+    assert!(!matcher.accept_string("abbx", false));
+    let diagnostics = matcher.rejection_diagnostics().expect("rejection should be diagnosable");
+    assert_eq!(diagnostics.longest_accepted_prefix(), "abb");
+    assert_eq!(diagnostics.expected_next_bytes(), &[b'd']);
+}
+
+#[test]
+#[ignore = "Jump-forward decoding: expose the deterministic continuation string from a matcher state needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_jump_forward_string_spans_multiple_deterministic_tokens() {
+    // find_jump_forward_string should return the full deterministic continuation even
+    // when it spans more than one upcoming token boundary, not just the next byte.
+    let ebnf = r#"root ::= "prefix_" "deterministic_tail" "\n"
+"#;
+    let grammar = Grammar::from_ebnf(ebnf, "root").unwrap();
+    let tokenizer_info =
+        TokenizerInfo::new::<&str>(&[], VocabType::RAW, &None, false).unwrap();
+    let mut matcher = matcher_from_grammar_with_tokenizer(&grammar, &tokenizer_info);
+
+    assert!(matcher.accept_string("prefix_", false));
+    assert_eq!(matcher.find_jump_forward_string(), "deterministic_tail\n");
+}
+
+#[test]
+#[ignore = "Matcher state rollback for speculative/backtracking decoders needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_rollback_to_initial_state() {
+    // Rolling back the full number of accepted tokens should return the matcher to
+    // exactly its freshly-reset state.
+    let ebnf = r#"root ::= "abb" | "abbd"
+"#;
+    let grammar = Grammar::from_ebnf(ebnf, "root").unwrap();
+    let tokenizer_info =
+        TokenizerInfo::new::<&str>(&[], VocabType::RAW, &None, false).unwrap();
+    let mut matcher = matcher_from_grammar_with_tokenizer_and_rollback(&grammar, &tokenizer_info, 10);
+
+    let bitmask_before = get_next_token_bitmask_helper(&mut matcher, 0);
+    assert!(matcher.accept_string("ab", false));
+    matcher.rollback(2);
+    let bitmask_after = get_next_token_bitmask_helper(&mut matcher, 0);
+    assert_eq!(bitmask_before, bitmask_after);
+}
+
+#[test]
+#[ignore = "Checkpoint/rollback on GrammarMatcher for speculative and jump-forward decoding needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_checkpoint_rollback_undoes_speculative_tokens() {
+    // A checkpoint/rollback pair lets a caller tentatively accept a batch of
+    // speculatively-decoded tokens and discard back to a known-good position if the
+    // draft is rejected, without having to track how many tokens were accepted.
+    let ebnf = r#"root ::= "abb" | "abbd"
+"#;
+    let grammar = Grammar::from_ebnf(ebnf, "root").unwrap();
+    let tokenizer_info = TokenizerInfo::new::<&str>(&[], VocabType::RAW, &None, false).unwrap();
+    let mut matcher =
+        matcher_from_grammar_with_tokenizer_and_rollback(&grammar, &tokenizer_info, 10);
+
+    let checkpoint = matcher.checkpoint();
+    let bitmask_before = get_next_token_bitmask_helper(&mut matcher, 0);
+    let terminated_before = matcher.is_terminated();
+
+    assert!(matcher.accept_string("ab", false));
+    matcher.rollback_to(checkpoint);
+
+    let bitmask_after = get_next_token_bitmask_helper(&mut matcher, 0);
+    assert_eq!(bitmask_before, bitmask_after);
+    assert_eq!(matcher.is_terminated(), terminated_before);
+}
+
+#[test]
+#[ignore = "Checkpoint/rollback on GrammarMatcher for speculative and jump-forward decoding needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_checkpoint_rollback_restores_jump_forward_string() {
+    // Pairs with the jump-forward test: after rolling back to a checkpoint, the next
+    // `find_jump_forward_string()` must match what it returned at checkpoint time.
+    let ebnf = r#"root ::= "prefix_" "deterministic_tail" "\n"
+"#;
+    let grammar = Grammar::from_ebnf(ebnf, "root").unwrap();
+    let tokenizer_info = TokenizerInfo::new::<&str>(&[], VocabType::RAW, &None, false).unwrap();
+    let mut matcher =
+        matcher_from_grammar_with_tokenizer_and_rollback(&grammar, &tokenizer_info, 10);
+
+    assert!(matcher.accept_string("prefix_", false));
+    let checkpoint = matcher.checkpoint();
+    let jump_forward_before = matcher.find_jump_forward_string();
+
+    // Speculatively walk further forward, then discard the speculation.
+    assert!(matcher.accept_string("determ", false));
+    matcher.rollback_to(checkpoint);
+
+    assert_eq!(matcher.find_jump_forward_string(), jump_forward_before);
+}
+
+#[test]
+#[ignore = "Batched `fill_next_token_bitmask` across a batch dimension in one call needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+#[cfg(feature = "hf")]
+fn test_batch_fill_next_token_bitmask_single_call_mixed_vocab_sizes() {
+    // Every matcher in a batch call shares one bitmask tensor; matchers built from
+    // tokenizers of different vocab sizes must still each get their own correctly-sized
+    // slice within that single call.
+    let vocab_small: Vec<&str> = vec!["a", "b", "</s>"];
+    let vocab_large: Vec<&str> = vec!["a", "b", "c", "d", "</s>"];
+    let grammar = Grammar::from_ebnf(r#"root ::= "a""#, "root").unwrap();
+
+    let tok_small = TokenizerInfo::new(&vocab_small, VocabType::RAW, &None, false).unwrap();
+    let tok_large = TokenizerInfo::new(&vocab_large, VocabType::RAW, &None, false).unwrap();
+    let matchers = vec![
+        matcher_from_grammar_with_tokenizer(&grammar, &tok_small),
+        matcher_from_grammar_with_tokenizer(&grammar, &tok_large),
+    ];
+
+    let max_vocab_size = vocab_large.len();
+    let mut bitmask_data = allocate_token_bitmask(2, max_vocab_size);
+    let (mut tensor, _shape, _strides) =
+        create_bitmask_dltensor(&mut bitmask_data, 2, max_vocab_size);
+
+    let mut batch_matcher = BatchGrammarMatcher::new(1).unwrap();
+    batch_matcher.batch_fill_next_token_bitmask(&matchers, &mut tensor, None, false);
+}
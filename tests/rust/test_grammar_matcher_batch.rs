@@ -0,0 +1,111 @@
+mod test_utils;
+
+use serial_test::serial;
+use test_utils::*;
+use xgrammar::{
+    allocate_token_bitmask, create_bitmask_dltensor, Grammar, GrammarCompiler,
+    GrammarMatcherBatch, TokenizerInfo, VocabType,
+};
+
+const VOCAB: &[&str] = &["<s>", "a", "b", "c", "</s>"];
+
+fn tokenizer_info() -> TokenizerInfo {
+    TokenizerInfo::new(VOCAB, VocabType::RAW, &None, false).unwrap()
+}
+
+fn token_id(token: &str) -> i32 {
+    VOCAB.iter().position(|v| *v == token).unwrap() as i32
+}
+
+#[test]
+#[ignore = "Batched matcher for continuous-batching LLM serving needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_add_and_remove_sequence_reuses_slots() {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+
+    let mut batch = GrammarMatcherBatch::new();
+    let idx0 = batch.add_sequence(&compiled);
+    let idx1 = batch.add_sequence(&compiled);
+    assert_ne!(idx0, idx1);
+
+    batch.remove_sequence(idx0);
+    // A freed slot must be reused instead of growing the batch unboundedly.
+    let idx2 = batch.add_sequence(&compiled);
+    assert_eq!(idx2, idx0);
+}
+
+#[test]
+#[ignore = "Batched matcher for continuous-batching LLM serving needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_accept_token_and_is_terminated_per_sequence() {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+
+    let mut batch = GrammarMatcherBatch::new();
+    let fast = batch.add_sequence(&compiled);
+    let slow = batch.add_sequence(&compiled);
+
+    let id_a = token_id("a");
+    let id_b = token_id("b");
+
+    assert!(batch.accept_token(fast, id_a));
+    assert!(batch.accept_token(fast, id_b));
+    assert!(batch.is_terminated(fast));
+
+    // The `slow` sequence must not be affected by advancing `fast`.
+    assert!(!batch.is_terminated(slow));
+    assert!(batch.accept_token(slow, id_a));
+    assert!(!batch.is_terminated(slow));
+}
+
+#[test]
+#[ignore = "Batched matcher for continuous-batching LLM serving needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_batch_fill_next_token_bitmask_writes_each_active_row() {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" | "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+
+    let mut batch = GrammarMatcherBatch::new();
+    let idx0 = batch.add_sequence(&compiled);
+    let idx1 = batch.add_sequence(&compiled);
+
+    let vocab_size = tokenizer_info.vocab_size();
+    let mut bitmask_data = allocate_token_bitmask(2, vocab_size);
+    let (mut tensor, _shape, _strides) = create_bitmask_dltensor(&mut bitmask_data, 2, vocab_size);
+
+    batch.fill_next_token_bitmask(&mut tensor, &[idx0, idx1]);
+
+    let slice_len = bitmask_data.len() / 2;
+    let row0 = &bitmask_data[..slice_len];
+    let row1 = &bitmask_data[slice_len..];
+    // Both rows start from the same grammar, so the same tokens should be allowed.
+    assert_eq!(row0, row1);
+}
+
+#[test]
+#[ignore = "Batched matcher for continuous-batching LLM serving needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_removed_sequence_slot_excluded_from_batch_fill() {
+    let tokenizer_info = tokenizer_info();
+    let grammar = Grammar::from_ebnf(r#"root ::= "a" "b""#, "root").unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+
+    let mut batch = GrammarMatcherBatch::new();
+    let idx0 = batch.add_sequence(&compiled);
+    batch.remove_sequence(idx0);
+
+    let vocab_size = tokenizer_info.vocab_size();
+    let mut bitmask_data = allocate_token_bitmask(1, vocab_size);
+    let (mut tensor, _shape, _strides) = create_bitmask_dltensor(&mut bitmask_data, 1, vocab_size);
+
+    // Filling a batch with no active indices must be a no-op, not a panic on a freed slot.
+    batch.fill_next_token_bitmask(&mut tensor, &[]);
+}
@@ -331,6 +331,39 @@ fn test_json_pressure() {
     assert!(is_grammar_accept_string(&grammar, long_3k));
 }
 
+/// Guards the bulk literal/single-byte-character-class scan `accept_string` is expected to
+/// take when the automaton's only viable continuation is a long fixed run (a literal byte
+/// sequence or something like `ws ::= [ \n\t]*`): whether that run is consumed one token at
+/// a time or via a vectorized scan, the accepted/rejected outcome must agree.
+#[test]
+#[ignore = "SIMD-accelerated literal prefix scanning in `accept_string` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_long_literal_and_whitespace_runs_match_incrementally_and_in_bulk() {
+    let grammar_str = r#"
+        root ::= "x" ws "needle" ws
+        ws ::= [ \n\t]*
+    "#;
+    let grammar = Grammar::from_ebnf(grammar_str, "root").unwrap();
+
+    let long_ws = " \n\t".repeat(2000);
+    let input = format!("x{long_ws}needle{long_ws}");
+    assert!(is_grammar_accept_string(&grammar, &input));
+
+    // A single disallowed byte buried deep in an otherwise-matching long run must still be
+    // detected, whether or not the run up to that point was consumed in one bulk step.
+    let mut broken_ws = " \n\t".repeat(2000);
+    broken_ws.insert(1500, 'z');
+    let broken_input = format!("x{broken_ws}needle{long_ws}");
+    assert!(!is_grammar_accept_string(&grammar, &broken_input));
+
+    // Feeding the long run across several `accept_string` calls (forcing the matcher to
+    // stop and resume mid-run) must accept the same input as a single bulk call.
+    let mut matcher = matcher_from_grammar(&grammar);
+    assert!(matcher.accept_string(&format!("x{long_ws}"), false));
+    assert!(matcher.accept_string("needle", false));
+    assert!(matcher.accept_string(&long_ws, false));
+    assert!(matcher.is_terminated());
+}
+
 #[test]
 fn test_nullable_grammar() {
     let grammar_str = r#"
@@ -687,6 +720,60 @@ fn test_positive_utf8_character_class_with_quantifier() {
     assert!(!is_grammar_accept_string(&grammar, "hello!")); // with special char
 }
 
+#[test]
+#[ignore = "ASCII/Unicode case-insensitive matching flag for character classes and literals needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_case_insensitive_character_class_flag() {
+    // `(?i)` scoping a character class should fold both directions of ASCII case,
+    // unlike the case-sensitive class in `test_positive_utf8_character_class_with_quantifier`.
+    let ebnf_grammar_str = "root ::= (?i)[a-z]+";
+    let grammar = Grammar::from_ebnf(ebnf_grammar_str, "root").unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, "hello"));
+    assert!(is_grammar_accept_string(&grammar, "HELLO"));
+    assert!(is_grammar_accept_string(&grammar, "HeLLo"));
+    assert!(!is_grammar_accept_string(&grammar, "hello1"));
+}
+
+#[test]
+#[ignore = "ASCII/Unicode case-insensitive matching flag for character classes and literals needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_case_insensitive_literal_flag() {
+    let ebnf_grammar_str = r#"root ::= (?i)"Hello""#;
+    let grammar = Grammar::from_ebnf(ebnf_grammar_str, "root").unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, "Hello"));
+    assert!(is_grammar_accept_string(&grammar, "HELLO"));
+    assert!(is_grammar_accept_string(&grammar, "hello"));
+    assert!(is_grammar_accept_string(&grammar, "hELLo"));
+    assert!(!is_grammar_accept_string(&grammar, "Hell0"));
+}
+
+#[test]
+#[ignore = "ASCII/Unicode case-insensitive matching flag for character classes and literals needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_case_insensitive_flag_does_not_leak_outside_scope() {
+    // The `(?i)` flag should scope to the subexpression it annotates, so a sibling
+    // rule referenced outside that scope stays case-sensitive.
+    let ebnf_grammar_str = r#"root ::= (?i)"yes" rest
+rest ::= "no"
+"#;
+    let grammar = Grammar::from_ebnf(ebnf_grammar_str, "root").unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, "YESno"));
+    assert!(!is_grammar_accept_string(&grammar, "YESNO"));
+}
+
+#[test]
+#[ignore = "ASCII/Unicode case-insensitive matching flag for character classes and literals needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_case_insensitive_unicode_simple_folding() {
+    // Full Unicode simple case folding applies to single-codepoint mappings, so a
+    // Cyrillic range under `(?i)` also matches its uppercase counterparts.
+    let ebnf_grammar_str = "root ::= (?i)[а-я]+";
+    let grammar = Grammar::from_ebnf(ebnf_grammar_str, "root").unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, "привет"));
+    assert!(is_grammar_accept_string(&grammar, "ПРИВЕТ"));
+    assert!(is_grammar_accept_string(&grammar, "ПриВет"));
+}
+
 #[test]
 #[serial]
 #[cfg(feature = "hf")]
@@ -703,3 +790,315 @@ fn test_not_neighbour_character_class() {
         create_bitmask_dltensor(&mut bitmask_data, 1, tokenizer_info.vocab_size());
     matcher.fill_next_token_bitmask(&mut tensor, 0, false);
 }
+
+#[test]
+#[ignore = "Add an ABNF (RFC 5234) front-end: `Grammar::from_abnf` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_abnf_basic_alternation_and_concatenation() {
+    let abnf = r#"
+        root = "a" / "b" concat
+        concat = "c" "d"
+    "#;
+    let grammar = Grammar::from_abnf(abnf, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "a"));
+    assert!(is_grammar_accept_string(&grammar, "bcd"));
+    assert!(!is_grammar_accept_string(&grammar, "b"));
+    assert!(!is_grammar_accept_string(&grammar, "ab"));
+}
+
+#[test]
+#[ignore = "Add an ABNF (RFC 5234) front-end: `Grammar::from_abnf` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_abnf_grouping_optional_and_repetition() {
+    let abnf = r#"
+        root = group [opt] 2*3rep
+        group = ("x" / "y")
+        opt = "?"
+        rep = "z"
+    "#;
+    let grammar = Grammar::from_abnf(abnf, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "xzz"));
+    assert!(is_grammar_accept_string(&grammar, "y?zzz"));
+    assert!(!is_grammar_accept_string(&grammar, "x"));
+    assert!(!is_grammar_accept_string(&grammar, "xzzzz"));
+}
+
+#[test]
+#[ignore = "Add an ABNF (RFC 5234) front-end: `Grammar::from_abnf` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_abnf_numeric_terminals() {
+    let abnf = r#"
+        root = single / dotted / ranged
+        single = %d97
+        dotted = %d13.10
+        ranged = %x30-39
+    "#;
+    let grammar = Grammar::from_abnf(abnf, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "a"));
+    assert!(is_grammar_accept_string(&grammar, "\r\n"));
+    assert!(is_grammar_accept_string(&grammar, "5"));
+    assert!(!is_grammar_accept_string(&grammar, "q"));
+}
+
+#[test]
+#[ignore = "Add an ABNF (RFC 5234) front-end: `Grammar::from_abnf` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_abnf_case_sensitivity_and_core_rules() {
+    // Bare quoted literals are case-insensitive by default in ABNF; %s forces
+    // case-sensitivity, and DIGIT is one of the predefined core rules.
+    let abnf = r#"
+        root = "select" SP %s"FROM" SP DIGIT
+    "#;
+    let grammar = Grammar::from_abnf(abnf, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "SELECT FROM 1"));
+    assert!(is_grammar_accept_string(&grammar, "select FROM 9"));
+    assert!(!is_grammar_accept_string(&grammar, "select from 1"));
+}
+
+#[test]
+#[ignore = "Add an ABNF (RFC 5234) front-end: `Grammar::from_abnf` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_abnf_incremental_alternation() {
+    let abnf = r#"
+        root = "a"
+        root =/ "b"
+        root =/ "c"
+    "#;
+    let grammar = Grammar::from_abnf(abnf, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "a"));
+    assert!(is_grammar_accept_string(&grammar, "b"));
+    assert!(is_grammar_accept_string(&grammar, "c"));
+    assert!(!is_grammar_accept_string(&grammar, "d"));
+}
+
+#[test]
+#[ignore = "Compile a regex directly into a matchable grammar via `Grammar::from_regex` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_regex_literals_dot_and_character_classes() {
+    let grammar = Grammar::from_regex(r"a.c[0-9][^xyz]", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "abc5q"));
+    assert!(is_grammar_accept_string(&grammar, "a c0w"));
+    assert!(!is_grammar_accept_string(&grammar, "abcaz"));
+    assert!(!is_grammar_accept_string(&grammar, "abc5x"));
+}
+
+#[test]
+#[ignore = "Compile a regex directly into a matchable grammar via `Grammar::from_regex` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_regex_quantifiers() {
+    let grammar = Grammar::from_regex(r"a*b+c?d{2}e{1,3}", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "bddee"));
+    assert!(is_grammar_accept_string(&grammar, "aaabcdde"));
+    assert!(is_grammar_accept_string(&grammar, "bddeee"));
+    assert!(!is_grammar_accept_string(&grammar, "bd")); // d{2} requires exactly 2
+    assert!(!is_grammar_accept_string(&grammar, "bddeeee")); // e{1,3} caps at 3
+}
+
+#[test]
+#[ignore = "Compile a regex directly into a matchable grammar via `Grammar::from_regex` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_regex_alternation_and_grouping() {
+    let grammar = Grammar::from_regex(r"(cat|dog)s?", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "cat"));
+    assert!(is_grammar_accept_string(&grammar, "dogs"));
+    assert!(!is_grammar_accept_string(&grammar, "cats?"));
+    assert!(!is_grammar_accept_string(&grammar, "catdog"));
+}
+
+#[test]
+#[ignore = "Compile a regex directly into a matchable grammar via `Grammar::from_regex` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_regex_utf8_character_classes() {
+    // Mirrors the grammar's existing Cyrillic/CJK/emoji range handling.
+    let grammar = Grammar::from_regex(r"[а-я]+", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "привет"));
+    assert!(!is_grammar_accept_string(&grammar, "hello"));
+}
+
+#[test]
+#[ignore = "Compile a regex directly into a matchable grammar via `Grammar::from_regex` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_regex_anchors_are_implicit_full_match() {
+    // The matcher validates whole strings, so explicit ^/$ anchors are accepted but
+    // redundant with the implicit full-match semantics.
+    let grammar = Grammar::from_regex(r"^abc$", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "abc"));
+    assert!(!is_grammar_accept_string(&grammar, "xabc"));
+    assert!(!is_grammar_accept_string(&grammar, "abcx"));
+}
+
+#[test]
+#[ignore = "Case-insensitive string literals in EBNF needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_case_insensitive_string_literal_suffix() {
+    let grammar = Grammar::from_ebnf(r#"root ::= "json"i"#, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "json"));
+    assert!(is_grammar_accept_string(&grammar, "JSON"));
+    assert!(is_grammar_accept_string(&grammar, "Json"));
+    assert!(is_grammar_accept_string(&grammar, "jSoN"));
+    assert!(!is_grammar_accept_string(&grammar, "jsonx"));
+}
+
+#[test]
+#[ignore = "Case-insensitive string literals in EBNF needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_case_insensitive_string_literal_prefix() {
+    let grammar = Grammar::from_ebnf(r#"root ::= %i"select""#, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "select"));
+    assert!(is_grammar_accept_string(&grammar, "SELECT"));
+    assert!(is_grammar_accept_string(&grammar, "Select"));
+}
+
+#[test]
+#[ignore = "Case-insensitive string literals in EBNF needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_case_insensitive_literal_composes_with_repetition_and_alternation() {
+    let grammar_str = r#"
+        root ::= ("true"i | "false"i) {1,2}
+    "#;
+    let grammar = Grammar::from_ebnf(grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "true"));
+    assert!(is_grammar_accept_string(&grammar, "TRUEfalse"));
+    assert!(is_grammar_accept_string(&grammar, "FalseTrue"));
+    assert!(!is_grammar_accept_string(&grammar, "TRUEfalsetrue"));
+}
+
+#[test]
+#[ignore = "Case-insensitive string literals in EBNF needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_case_insensitive_literal_leaves_non_letters_and_multibyte_untouched() {
+    let grammar = Grammar::from_ebnf(r#"root ::= "a-1é"i"#, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "a-1é"));
+    assert!(is_grammar_accept_string(&grammar, "A-1é"));
+    // The accented character is left as a literal, not case-expanded.
+    assert!(!is_grammar_accept_string(&grammar, "A-1É"));
+}
+
+#[test]
+#[ignore = "Grammar composition / merge API for building grammars from reusable fragments needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_merge_unions_same_named_rules_instead_of_erroring() {
+    let numbers = Grammar::from_ebnf("numbers ::= digit\ndigit ::= [0-9]\nshared ::= [0-9]", "numbers").unwrap();
+    let words = Grammar::from_ebnf("words ::= letter\nletter ::= [a-z]\nshared ::= [a-z]", "words").unwrap();
+
+    // Merging two fragments that both define `shared` must union the alternatives
+    // rather than reject the redefinition, mirroring ABNF's `=/` semantics.
+    let merged = Grammar::merge(&[&numbers, &words], "shared").unwrap();
+    assert!(is_grammar_accept_string(&merged, "5"));
+    assert!(is_grammar_accept_string(&merged, "q"));
+    assert!(!is_grammar_accept_string(&merged, "5q"));
+}
+
+#[test]
+#[ignore = "Grammar composition / merge API for building grammars from reusable fragments needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_merge_resolves_cross_grammar_references_under_new_root() {
+    let strings = Grammar::from_ebnf(
+        r#"basic_string ::= [\"] basic_string_1 [\"]
+basic_string_1 ::= "" | [^"\\] basic_string_1"#,
+        "basic_string",
+    )
+    .unwrap();
+    let object = Grammar::from_ebnf(
+        r#"basic_object ::= "{" (basic_string ":" basic_string)? "}""#,
+        "basic_object",
+    )
+    .unwrap();
+
+    // `basic_object` references `basic_string`, which only exists in `strings` — the
+    // merged grammar must resolve that reference across the two source fragments.
+    let merged = Grammar::merge(&[&strings, &object], "basic_object").unwrap();
+    assert!(is_grammar_accept_string(&merged, r#"{"a":"b"}"#));
+    assert!(is_grammar_accept_string(&merged, "{}"));
+    assert!(!is_grammar_accept_string(&merged, r#"{"a"}"#));
+}
+
+/// Feed `bytes` into `matcher` one at a time via `accept_string` on a (possibly invalid,
+/// mid-code-point) single-byte slice, mirroring the byte-by-byte feeding pattern
+/// `test_fill_next_token_bitmask_unicode_char_class` above uses for valid UTF-8.
+fn accept_bytes_one_at_a_time(matcher: &mut GrammarMatcher, bytes: &[u8]) -> bool {
+    for b in bytes {
+        let s = unsafe { std::str::from_utf8_unchecked(std::slice::from_ref(b)) };
+        if !matcher.accept_string(s, false) {
+            return false;
+        }
+    }
+    true
+}
+
+#[test]
+#[ignore = "Incremental UTF-8 validation DFA for byte-level matching in GrammarMatcher needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_utf8_dfa_rejects_malformed_byte_sequences() {
+    let grammar = Grammar::from_ebnf("root ::= [a-zа-я一-龥]+", "root").unwrap();
+
+    // A lone continuation byte (0x80..=0xBF) is never valid at the start of a code point.
+    let mut matcher = matcher_from_grammar(&grammar);
+    assert!(!accept_bytes_one_at_a_time(&mut matcher, &[0x80]));
+
+    // Overlong encoding of U+002F ('/') as a 2-byte sequence (0xC0 0xAF) must be rejected,
+    // not silently accepted as '/'.
+    let mut matcher = matcher_from_grammar(&grammar);
+    assert!(!accept_bytes_one_at_a_time(&mut matcher, &[0xC0, 0xAF]));
+
+    // A truncated 3-byte sequence (CJK lead byte with only one continuation byte, then a
+    // byte that can't continue it) must be rejected rather than silently resynchronizing.
+    let mut matcher = matcher_from_grammar(&grammar);
+    assert!(!accept_bytes_one_at_a_time(&mut matcher, &[0xE4, 0xB8, 0x41]));
+}
+
+#[test]
+#[ignore = "Incremental UTF-8 validation DFA for byte-level matching in GrammarMatcher needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_utf8_dfa_reports_mid_codepoint_state() {
+    let grammar = Grammar::from_ebnf("root ::= [一-龥]+", "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar);
+
+    // '中' is E4 B8 AD; after the first two bytes the DFA should still be mid-code-point
+    // (needs more bytes), and only complete once the third byte lands.
+    assert!(matcher.accept_string(
+        unsafe { std::str::from_utf8_unchecked(&[0xE4]) },
+        false
+    ));
+    assert!(matcher.ends_mid_codepoint());
+
+    assert!(matcher.accept_string(
+        unsafe { std::str::from_utf8_unchecked(&[0xB8]) },
+        false
+    ));
+    assert!(matcher.ends_mid_codepoint());
+
+    assert!(matcher.accept_string(
+        unsafe { std::str::from_utf8_unchecked(&[0xAD]) },
+        false
+    ));
+    assert!(!matcher.ends_mid_codepoint());
+}
+
+#[test]
+#[ignore = "Unicode script/category escapes in EBNF character classes needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_unicode_property_escape_script_han() {
+    // \p{Han} should expand to the same codepoint ranges as hand-written CJK blocks.
+    let grammar = Grammar::from_ebnf(r"root ::= [\p{Han}]+", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "你好世界"));
+    assert!(!is_grammar_accept_string(&grammar, "hello"));
+    assert!(!is_grammar_accept_string(&grammar, "ひらがな"));
+}
+
+#[test]
+#[ignore = "Unicode script/category escapes in EBNF character classes needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_unicode_property_escape_multiple_scripts_in_one_class() {
+    let grammar = Grammar::from_ebnf(r"root ::= [\p{Cyrillic}\p{Latin}]+", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "hello"));
+    assert!(is_grammar_accept_string(&grammar, "привет"));
+    assert!(is_grammar_accept_string(&grammar, "helloпривет"));
+    assert!(!is_grammar_accept_string(&grammar, "你好"));
+}
+
+#[test]
+#[ignore = "Unicode script/category escapes in EBNF character classes needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_unicode_property_escape_general_categories() {
+    let grammar = Grammar::from_ebnf(r"root ::= [\p{L}\p{Nd}]+", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "abc123"));
+    assert!(!is_grammar_accept_string(&grammar, "abc 123")); // Zs not included
+}
+
+#[test]
+#[ignore = "Unicode script/category escapes in EBNF character classes needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_unicode_property_escape_negation() {
+    let grammar = Grammar::from_ebnf(r"root ::= [\P{Han}]+", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "hello"));
+    assert!(!is_grammar_accept_string(&grammar, "你好"));
+}
+
+#[test]
+#[ignore = "Unicode script/category escapes in EBNF character classes needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_unicode_property_escape_kana_and_hangul() {
+    let grammar = Grammar::from_ebnf(r"root ::= [\p{Hiragana}\p{Katakana}\p{Hangul}]+", "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "ひらがな"));
+    assert!(is_grammar_accept_string(&grammar, "カタカナ"));
+    assert!(is_grammar_accept_string(&grammar, "한글"));
+    assert!(!is_grammar_accept_string(&grammar, "你好"));
+}
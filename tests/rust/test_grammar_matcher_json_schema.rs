@@ -5,7 +5,7 @@ use test_utils::*;
 #[cfg(feature = "hf")]
 use xgrammar::allocate_token_bitmask;
 use xgrammar::{
-    Grammar, GrammarCompiler, GrammarMatcher, TokenizerInfo, VocabType,
+    FormatPattern, Grammar, GrammarCompiler, GrammarMatcher, TokenizerInfo, VocabType,
 };
 #[cfg(feature = "hf")]
 use serde_json::json;
@@ -943,3 +943,184 @@ fn test_json_schema_number_without_constraint() {
     assert!(!is_grammar_accept_string(&grammar, r#"{"value": "abc"}"#));
 }
 
+#[test]
+#[ignore = "Extend numeric constraints with exclusiveMinimum/exclusiveMaximum and multipleOf needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_json_schema_exclusive_minimum_maximum() {
+    let schema = r#"{"type":"object","properties":{"value":{"type":"integer","exclusiveMinimum":0,"exclusiveMaximum":10}},"required":["value"]}"#;
+    let grammar = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    )
+    .unwrap();
+
+    // The boundary values themselves must be rejected...
+    assert!(!is_grammar_accept_string(&grammar, r#"{"value": 0}"#));
+    assert!(!is_grammar_accept_string(&grammar, r#"{"value": 10}"#));
+    // ...while values strictly inside the open interval are accepted.
+    assert!(is_grammar_accept_string(&grammar, r#"{"value": 1}"#));
+    assert!(is_grammar_accept_string(&grammar, r#"{"value": 9}"#));
+}
+
+#[test]
+#[ignore = "Extend numeric constraints with exclusiveMinimum/exclusiveMaximum and multipleOf needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_json_schema_exclusive_minimum_allows_fractional_boundary_neighbor() {
+    let schema = r#"{"type":"object","properties":{"value":{"type":"number","exclusiveMinimum":0}},"required":["value"]}"#;
+    let grammar = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(!is_grammar_accept_string(&grammar, r#"{"value": 0}"#));
+    assert!(!is_grammar_accept_string(&grammar, r#"{"value": 0.0}"#));
+    assert!(is_grammar_accept_string(&grammar, r#"{"value": 0.0001}"#));
+}
+
+#[test]
+#[ignore = "Extend numeric constraints with exclusiveMinimum/exclusiveMaximum and multipleOf needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_json_schema_multiple_of_integer() {
+    let schema = r#"{"type":"object","properties":{"value":{"type":"integer","multipleOf":5}},"required":["value"]}"#;
+    let grammar = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, r#"{"value": 0}"#));
+    assert!(is_grammar_accept_string(&grammar, r#"{"value": 5}"#));
+    assert!(is_grammar_accept_string(&grammar, r#"{"value": -15}"#));
+    assert!(!is_grammar_accept_string(&grammar, r#"{"value": 7}"#));
+}
+
+#[test]
+#[ignore = "Extend numeric constraints with exclusiveMinimum/exclusiveMaximum and multipleOf needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_json_schema_multiple_of_decimal_scaling() {
+    // multipleOf: 0.01 is the common "cents" case: normalize to an integer multiple
+    // check on the value scaled by 100, bounding the fractional digit count to 2.
+    let schema = r#"{"type":"object","properties":{"value":{"type":"number","multipleOf":0.01}},"required":["value"]}"#;
+    let grammar = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, r#"{"value": 1.23}"#));
+    assert!(is_grammar_accept_string(&grammar, r#"{"value": 1.0}"#));
+    assert!(!is_grammar_accept_string(&grammar, r#"{"value": 1.234}"#));
+}
+
+#[test]
+#[ignore = "Extend numeric constraints with exclusiveMinimum/exclusiveMaximum and multipleOf needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_json_schema_multiple_of_zero_rejected() {
+    let schema = r#"{"type":"object","properties":{"value":{"type":"integer","multipleOf":0}},"required":["value"]}"#;
+    let result = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    );
+
+    assert!(result.is_err(), "multipleOf: 0 must be rejected with a clear error");
+}
+
+fn raw_tokenizer_info() -> TokenizerInfo {
+    let empty_vocab: Vec<&str> = vec![];
+    TokenizerInfo::new(&empty_vocab, VocabType::RAW, &None, false).unwrap()
+}
+
+#[test]
+#[ignore = "Pluggable custom `format` keyword registry for JSON-schema compilation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_register_custom_format_constrains_string_field() {
+    let tokenizer_info = raw_tokenizer_info();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    compiler.register_format("phone", FormatPattern::Regex(r"\d{3}-\d{3}-\d{4}".to_string()));
+
+    let schema = r#"{"type":"object","properties":{"value":{"type":"string","format":"phone"}},"required":["value"]}"#;
+    let compiled = compiler
+        .compile_json_schema(schema, true, None, None::<(&str, &str)>, true, None)
+        .unwrap();
+    let mut matcher = GrammarMatcher::new(&compiled, None, true, -1).unwrap();
+
+    assert!(matcher.accept_string(r#"{"value": "555-123-4567"}"#, false));
+    assert!(matcher.is_terminated());
+
+    let mut rejecting_matcher = GrammarMatcher::new(&compiled, None, true, -1).unwrap();
+    assert!(!rejecting_matcher.accept_string(r#"{"value": "not-a-phone"}"#, false));
+}
+
+#[test]
+#[ignore = "Pluggable custom `format` keyword registry for JSON-schema compilation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_register_custom_format_with_ebnf_fragment() {
+    let tokenizer_info = raw_tokenizer_info();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    compiler.register_format(
+        "traffic-light",
+        FormatPattern::Ebnf(r#""red" | "yellow" | "green""#.to_string()),
+    );
+
+    let schema = r#"{"type":"object","properties":{"value":{"type":"string","format":"traffic-light"}},"required":["value"]}"#;
+    let compiled = compiler
+        .compile_json_schema(schema, true, None, None::<(&str, &str)>, true, None)
+        .unwrap();
+
+    let mut matcher = GrammarMatcher::new(&compiled, None, true, -1).unwrap();
+    assert!(matcher.accept_string(r#"{"value": "red"}"#, false));
+
+    let mut rejecting_matcher = GrammarMatcher::new(&compiled, None, true, -1).unwrap();
+    assert!(!rejecting_matcher.accept_string(r#"{"value": "blue"}"#, false));
+}
+
+#[test]
+#[ignore = "Pluggable custom `format` keyword registry for JSON-schema compilation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_register_format_overrides_builtin() {
+    // Registering under a built-in name (e.g. "uuid") must replace the built-in
+    // definition rather than erroring or being ignored.
+    let tokenizer_info = raw_tokenizer_info();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    compiler.register_format("uuid", FormatPattern::Regex(r"id-\d+".to_string()));
+
+    let schema = r#"{"type":"object","properties":{"value":{"type":"string","format":"uuid"}},"required":["value"]}"#;
+    let compiled = compiler
+        .compile_json_schema(schema, true, None, None::<(&str, &str)>, true, None)
+        .unwrap();
+
+    let mut matcher = GrammarMatcher::new(&compiled, None, true, -1).unwrap();
+    assert!(matcher.accept_string(r#"{"value": "id-42"}"#, false));
+
+    // The built-in UUID shape must no longer be accepted once overridden.
+    let mut rejecting_matcher = GrammarMatcher::new(&compiled, None, true, -1).unwrap();
+    assert!(!rejecting_matcher
+        .accept_string(r#"{"value": "01234567-89AB-CDEF-abcd-ef0123456789"}"#, false));
+}
+
@@ -0,0 +1,94 @@
+mod test_utils;
+
+use serial_test::serial;
+use xgrammar::{Grammar, GrammarCompiler, GrammarMatcher, TokenizerInfo, VocabType};
+
+fn matcher_from_grammar(grammar: &Grammar) -> GrammarMatcher {
+    let empty_vocab: Vec<&str> = vec![];
+    let tokenizer_info = TokenizerInfo::new(&empty_vocab, VocabType::RAW, &None, false).unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(grammar).unwrap();
+    GrammarMatcher::new(&compiled, None, true, -1)
+        .unwrap()
+        .with_span_recording(true)
+}
+
+#[test]
+#[ignore = "Expose parse spans / named captures from GrammarMatcher::accept_string needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_json_grammar_spans_cover_key_values() {
+    let grammar = Grammar::builtin_json_grammar();
+    let mut matcher = matcher_from_grammar(&grammar);
+    let input = r#"{"id": 1,"name": "Example"}"#;
+    assert!(matcher.accept_string(input, false));
+
+    let spans = matcher.matched_spans();
+    assert!(!spans.is_empty(), "span recording should be opt-in but non-empty once enabled");
+
+    // Every recorded span must be a valid, non-inverted range into the consumed input.
+    for span in spans.iter() {
+        assert!(span.start <= span.end);
+        assert!(span.end <= input.len());
+    }
+
+    let name_value_span = spans
+        .iter()
+        .find(|s| &input[s.start..s.end] == "\"Example\"")
+        .expect("expected a span covering the \"name\" string value");
+    assert_eq!(&input[name_value_span.start..name_value_span.end], "\"Example\"");
+}
+
+#[test]
+#[ignore = "Expose parse spans / named captures from GrammarMatcher::accept_string needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_spans_are_incremental_across_accept_calls() {
+    let grammar = Grammar::builtin_json_grammar();
+    let mut matcher = matcher_from_grammar(&grammar);
+
+    assert!(matcher.accept_string(r#"{"id""#, false));
+    let spans_after_first = matcher.matched_spans().len();
+
+    assert!(matcher.accept_string(r#": 1}"#, false));
+    let spans_after_second = matcher.matched_spans().len();
+
+    assert!(
+        spans_after_second >= spans_after_first,
+        "spans must accumulate as more tokens are accepted, not reset"
+    );
+}
+
+#[test]
+#[ignore = "Expose parse spans / named captures from GrammarMatcher::accept_string needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_spans_survive_rollback() {
+    let grammar = Grammar::builtin_json_grammar();
+    let mut matcher = matcher_from_grammar(&grammar);
+
+    assert!(matcher.accept_string(r#"{"id": 1"#, false));
+    let spans_before = matcher.matched_spans();
+
+    // Accept a byte that extends the object, then roll back to before it was accepted;
+    // the spans recorded for the state we rolled back to must be exactly restored.
+    assert!(matcher.accept_string(",", false));
+    matcher.rollback(1);
+
+    assert_eq!(matcher.matched_spans(), spans_before);
+}
+
+#[test]
+#[ignore = "Expose parse spans / named captures from GrammarMatcher::accept_string needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_span_recording_is_opt_in() {
+    let grammar = Grammar::builtin_json_grammar();
+    let empty_vocab: Vec<&str> = vec![];
+    let tokenizer_info = TokenizerInfo::new(&empty_vocab, VocabType::RAW, &None, false).unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(&grammar).unwrap();
+    let mut matcher = GrammarMatcher::new(&compiled, None, true, -1).unwrap();
+
+    assert!(matcher.accept_string(r#"{"id": 1}"#, false));
+    assert!(
+        matcher.matched_spans().is_empty(),
+        "without with_span_recording(true), no spans should be recorded"
+    );
+}
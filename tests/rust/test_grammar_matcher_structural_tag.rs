@@ -456,4 +456,39 @@ fn test_pressure_structural_tag() {
     }
 }
 
+#[test]
+#[serial]
+fn test_structural_tag_parse_tree() {
+    let schema = json!({
+        "type": "object",
+        "properties": {"arg1": {"type": "string"}, "arg2": {"type": "integer"}},
+        "required": ["arg1", "arg2"]
+    });
+    let structural_tag = json!({
+        "type": "structural_tag",
+        "format": {
+            "type": "triggered_tags",
+            "triggers": ["<function=f"],
+            "tags": [
+                {"begin": "<function=f1>", "content": {"type": "json_schema", "json_schema": schema}, "end": "</function>"}
+            ]
+        }
+    });
+
+    let grammar = Grammar::from_structural_tag(&structural_tag.to_string()).unwrap();
+    let input = r#"<function=f1>{"arg1": "abc", "arg2": 1}</function>"#;
+    assert!(is_grammar_accept_string(&grammar, input));
+
+    // `parse` walks the same PDA as `accept_string` but returns the tree of rules and
+    // sub-matches that were taken to accept `input`, rather than just a bool.
+    let tree = grammar.parse(input).expect("accepted input must produce a parse tree");
+    assert_eq!(tree.text(), input);
+    assert!(
+        tree.children().iter().any(|child| child.rule_name() == "triggered_tags"),
+        "expected the triggered_tags dispatch to appear in the parse tree, got {tree:?}"
+    );
+
+    assert!(grammar.parse("not a match at all").is_none());
+}
+
 
@@ -6,7 +6,10 @@ use serial_test::serial;
 use test_utils::*;
 use xgrammar::Grammar;
 use serde_json::{Value, json};
-use xgrammar::testing::{generate_float_range_regex, generate_range_regex, json_schema_to_ebnf};
+use xgrammar::testing::{
+    generate_float_range_regex, generate_float_range_regex_with_multiple_of, generate_range_regex,
+    generate_range_regex_with_multiple_of, json_schema_to_ebnf,
+};
 #[cfg(feature = "hf")]
 use xgrammar::{GrammarCompiler, GrammarMatcher, TokenizerInfo, VocabType};
 
@@ -170,6 +173,119 @@ fn test_non_strict() {
     ));
 }
 
+/// Test `additionalProperties` as a schema (not just a boolean), constraining the type
+/// of any extra key.
+#[test]
+#[ignore = "`patternProperties` and typed `additionalProperties` for objects needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_typed_additional_properties() {
+    let schema = json!({
+        "type": "object",
+        "properties": {"name": {"type": "string"}},
+        "required": ["name"],
+        "additionalProperties": {"type": "number"}
+    });
+
+    let instance_accepted = [
+        (r#"{"name": "Alice"}"#, true),
+        (r#"{"name": "Alice", "age": 30}"#, true),
+        (r#"{"name": "Alice", "age": "thirty"}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+/// Test `patternProperties`: extra keys matching a regex must conform to that pattern's
+/// subschema; unmatched keys fall through to `additionalProperties`.
+#[test]
+#[ignore = "`patternProperties` and typed `additionalProperties` for objects needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_pattern_properties_constrains_matching_keys() {
+    let schema = json!({
+        "type": "object",
+        "properties": {"name": {"type": "string"}},
+        "required": ["name"],
+        "patternProperties": {
+            "^S_": {"type": "string"},
+            "^N_": {"type": "number"}
+        },
+        "additionalProperties": false
+    });
+
+    let instance_accepted = [
+        (r#"{"name": "Alice", "S_nick": "Al"}"#, true),
+        (r#"{"name": "Alice", "N_age": 30}"#, true),
+        (r#"{"name": "Alice", "S_nick": 1}"#, false),
+        (r#"{"name": "Alice", "N_age": "thirty"}"#, false),
+        (r#"{"name": "Alice", "other": "x"}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "`patternProperties` and typed `additionalProperties` for objects needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_pattern_properties_falls_through_to_typed_additional_properties() {
+    let schema = json!({
+        "type": "object",
+        "patternProperties": {
+            "^S_": {"type": "string"}
+        },
+        "additionalProperties": {"type": "boolean"}
+    });
+
+    let instance_accepted = [
+        (r#"{"S_nick": "Al"}"#, true),
+        (r#"{"other": true}"#, true),
+        (r#"{"other": "not a bool"}"#, false),
+        (r#"{"S_nick": 1}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "`patternProperties` and typed `additionalProperties` for objects needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_pattern_properties_with_additional_properties_false_rejects_unsatisfiable_schema() {
+    // `additionalProperties: false` together with a non-empty `patternProperties` is not
+    // itself unsatisfiable (pattern-matched keys are still allowed); this only becomes an
+    // error when a declared `properties` key collides with both constraints in a way that
+    // can never be satisfied, e.g. a required property whose name matches no declared
+    // property and no pattern while additional properties are disallowed.
+    let schema_json = serde_json::to_string(&json!({
+        "type": "object",
+        "required": ["orphan"],
+        "patternProperties": {
+            "^S_": {"type": "string"}
+        },
+        "additionalProperties": false
+    }))
+    .expect("serialize schema");
+
+    let result = Grammar::from_json_schema(
+        &schema_json,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    );
+    match result {
+        Ok(_) => panic!("expected error for an unsatisfiable required/patternProperties combination"),
+        Err(err) => assert!(
+            err.contains("orphan") || err.contains("additionalProperties") || err.contains("patternProperties"),
+            "expected an error describing the unsatisfiable schema, got '{}'",
+            err
+        ),
+    }
+}
+
 /// Test enum and const constraints
 #[test]
 #[serial]
@@ -657,6 +773,89 @@ fn test_anyof_oneof() {
     assert!(!is_grammar_accept_string(&grammar, r#"null"#));
 }
 
+#[test]
+#[ignore = "Support `allOf` / `anyOf` / `oneOf` / `not` combinators with schema merging needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_one_of_produces_an_alternation_like_any_of() {
+    // The grammar has no way to enforce "exactly one branch matches" at parse time, so
+    // `oneOf` compiles to the same alternation `anyOf` does.
+    let schema = json!({
+        "oneOf": [
+            {"type": "string"},
+            {"type": "integer"}
+        ]
+    });
+
+    let instance_accepted = [
+        (r#""hello""#, true),
+        (r#"42"#, true),
+        (r#"true"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Support `allOf` / `anyOf` / `oneOf` / `not` combinators with schema merging needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_not_enum_excludes_listed_literals() {
+    let schema = json!({
+        "not": {"enum": ["red", "green"]}
+    });
+
+    let instance_accepted = [
+        (r#""red""#, false),
+        (r#""green""#, false),
+        (r#""blue""#, true),
+        (r#"42"#, true),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Support `allOf` / `anyOf` / `oneOf` / `not` combinators with schema merging needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_not_const_excludes_a_single_literal() {
+    let schema = json!({
+        "not": {"const": 0}
+    });
+
+    let instance_accepted = [(r#"0"#, false), (r#"1"#, true), (r#""0""#, true)];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Support `allOf` / `anyOf` / `oneOf` / `not` combinators with schema merging needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_not_with_unsupported_subschema_is_a_clear_error() {
+    // `not` over an arbitrary subschema (here a type constraint) isn't representable as
+    // grammar exclusion; this must fail loudly rather than silently ignoring the `not`.
+    let schema_json =
+        serde_json::to_string(&json!({"not": {"type": "string"}})).expect("serialize schema");
+    let result = Grammar::from_json_schema(
+        &schema_json,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    );
+    match result {
+        Ok(_) => panic!("expected an error for an unsupported `not` form"),
+        Err(err) => assert!(
+            err.contains("not"),
+            "expected an error describing the unsupported `not` schema, got '{}'",
+            err
+        ),
+    }
+}
+
 /// Test string with pattern restriction
 #[test]
 #[serial]
@@ -1099,6 +1298,86 @@ fn test_generate_range_regex() {
     );
 }
 
+#[test]
+#[ignore = "Add `multipleOf` support to numeric range regex generation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_generate_range_regex_with_multiple_of_enumerates_valid_multiples() {
+    assert_eq!(
+        generate_range_regex_with_multiple_of(Some(0), Some(20), 5).unwrap(),
+        r"^(0|5|10|15|20)$"
+    );
+}
+
+#[test]
+#[ignore = "Add `multipleOf` support to numeric range regex generation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_generate_range_regex_with_multiple_of_places_the_leading_minus_correctly() {
+    let regex = generate_range_regex_with_multiple_of(Some(-10), Some(10), 5).unwrap();
+    for value in ["-10", "-5", "0", "5", "10"] {
+        assert!(
+            regex.contains(value),
+            "expected '{}' to appear as an alternative in {}",
+            value,
+            regex
+        );
+    }
+    for value in ["-7", "3"] {
+        assert!(
+            !regex.contains(value),
+            "expected '{}' to not appear as an alternative in {}",
+            value,
+            regex
+        );
+    }
+}
+
+#[test]
+#[ignore = "Add `multipleOf` support to numeric range regex generation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_generate_range_regex_with_multiple_of_errors_when_enumeration_is_too_large() {
+    let result = generate_range_regex_with_multiple_of(Some(0), Some(1_000_000), 1);
+    assert!(
+        result.is_err(),
+        "expected an error when the multiple-of enumeration exceeds the threshold"
+    );
+}
+
+#[test]
+#[ignore = "Add `multipleOf` support to numeric range regex generation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_generate_range_regex_with_multiple_of_errors_on_unbounded_range() {
+    let result = generate_range_regex_with_multiple_of(None, None, 5);
+    assert!(
+        result.is_err(),
+        "expected an error since an unbounded range cannot be enumerated"
+    );
+}
+
+#[test]
+#[ignore = "Add `multipleOf` support to numeric range regex generation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_generate_float_range_regex_with_multiple_of_snaps_bounds_inward() {
+    // 0.15 does not evenly divide by 0.2, so the effective minimum snaps up to 0.2; 1.0 is
+    // already a multiple of 0.2, so the effective maximum stays put.
+    let regex = generate_float_range_regex_with_multiple_of(Some(0.15), Some(1.0), 0.2).unwrap();
+    assert!(regex.contains("0.2"));
+    assert!(regex.contains("1.0") || regex.contains("1\\.0"));
+    assert!(!regex.contains("0.15"));
+}
+
+#[test]
+#[ignore = "Add `multipleOf` support to numeric range regex generation needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_generate_float_range_regex_with_multiple_of_rejects_sub_precision_step() {
+    // The converter only supports six digits of fractional precision; a `multipleOf`
+    // finer than that can't be represented faithfully.
+    let result = generate_float_range_regex_with_multiple_of(Some(0.0), Some(1.0), 0.0000001);
+    assert!(
+        result.is_err(),
+        "expected an error for a multipleOf finer than six-digit precision"
+    );
+}
+
 #[test]
 #[serial]
 fn test_min_max_length() {
@@ -1443,6 +1722,54 @@ fn test_time_format() {
     assert!(!is_grammar_accept_string(&grammar, r#""00:60:00Z""#));
 }
 
+/// Test date-time format validation (RFC3339: date, "T", time)
+#[test]
+#[ignore = "Add `format` keyword support with built-in format→regex expansion in `from_json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_date_time_format() {
+    let schema = r#"{"type": "string", "format": "date-time"}"#;
+    let grammar = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, r#""2024-06-15T12:34:56Z""#));
+    assert!(is_grammar_accept_string(&grammar, r#""2024-06-15T12:34:56.7+08:09""#));
+    assert!(is_grammar_accept_string(&grammar, r#""0000-01-01T00:00:00Z""#));
+    assert!(!is_grammar_accept_string(&grammar, r#""2024-06-15 12:34:56Z""#));
+    assert!(!is_grammar_accept_string(&grammar, r#""2024-13-15T12:34:56Z""#));
+    assert!(!is_grammar_accept_string(&grammar, r#""2024-06-15T24:00:00Z""#));
+}
+
+/// An unrecognized `format` name must fall back to an unconstrained string rather than
+/// erroring, matching the "ignore unsupported formats" behavior.
+#[test]
+#[ignore = "Add `format` keyword support with built-in format→regex expansion in `from_json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_unknown_format_falls_back_to_unconstrained_string() {
+    let schema = r#"{"type": "string", "format": "not-a-real-format"}"#;
+    let grammar = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, r#""anything goes here""#));
+    assert!(is_grammar_accept_string(&grammar, r#""""#));
+    assert!(!is_grammar_accept_string(&grammar, r#"123"#));
+}
+
 #[test]
 #[serial]
 fn test_ipv6_format() {
@@ -1566,6 +1893,56 @@ fn test_hostname_format() {
     assert!(!is_grammar_accept_string(&grammar, r#""c-""#));
 }
 
+/// Test internationalized hostname format validation: like `hostname`, but each label may
+/// additionally contain non-ASCII Unicode characters (the pre-Punycode IDN form).
+#[test]
+#[ignore = "Expand the `format` keyword catalog: `duration`, `uri`/`uri-reference`, `json-pointer`, `regex`, `idn-hostname` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_idn_hostname_format() {
+    let schema = r#"{"type": "string", "format": "idn-hostname"}"#;
+    let grammar = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, r#""www.github.com""#));
+    assert!(is_grammar_accept_string(&grammar, r#""日本語.jp""#));
+    assert!(is_grammar_accept_string(&grammar, r#""münchen.de""#));
+    assert!(!is_grammar_accept_string(&grammar, r#"".""#));
+    assert!(!is_grammar_accept_string(&grammar, r#""-""#));
+    assert!(!is_grammar_accept_string(&grammar, r#""a.""#));
+}
+
+/// Test the `regex` format: the string's value must itself be a syntactically valid
+/// regular expression, not a string matching some fixed pattern.
+#[test]
+#[ignore = "Expand the `format` keyword catalog: `duration`, `uri`/`uri-reference`, `json-pointer`, `regex`, `idn-hostname` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_regex_format() {
+    let schema = r#"{"type": "string", "format": "regex"}"#;
+    let grammar = Grammar::from_json_schema(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, r#""^[a-z]+$""#));
+    assert!(is_grammar_accept_string(&grammar, r#""a|b""#));
+    assert!(!is_grammar_accept_string(&grammar, r#""[a-z""#));
+    assert!(!is_grammar_accept_string(&grammar, r#""(unclosed""#));
+}
+
 /// Test UUID format validation
 #[test]
 #[serial]
@@ -1771,3 +2148,348 @@ root ::= string
         check_schema_with_instance(&schema, &value, accepted, true, None, None, true);
     }
 }
+
+#[test]
+#[ignore = "Add JSON Schema `pattern` (regex) support with a regex-to-grammar compiler needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_pattern_format() {
+    // The `pattern` keyword should compile its regex into the grammar directly, so
+    // only strings matching the regex are accepted.
+    let instance_accepted = [
+        (r"abc123", true),
+        (r"abc", false),
+        (r"123abc", false),
+        (r"abcABC123", false),
+    ];
+    let schema = json!({"type": "string", "pattern": "^[a-z]+[0-9]+$"});
+
+    for (instance, accepted) in instance_accepted {
+        let value = format!("\"{}\"", instance);
+        check_schema_with_instance(&schema, &value, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Support the `pattern` keyword by compiling its regex into EBNF with shared-subrule extraction needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_pattern_bounded_repetition() {
+    // `{m,n}` must expand to m mandatory copies followed by (n - m) optional copies.
+    let instance_accepted = [
+        (r"ab", false),
+        (r"abc", true),
+        (r"abcc", true),
+        (r"abccc", true),
+        (r"abcccc", false),
+    ];
+    let schema = json!({"type": "string", "pattern": "^abc{1,3}$"});
+
+    for (instance, accepted) in instance_accepted {
+        let value = format!("\"{}\"", instance);
+        check_schema_with_instance(&schema, &value, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Support the `pattern` keyword by compiling its regex into EBNF with shared-subrule extraction needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_pattern_groups_and_alternation() {
+    let instance_accepted = [
+        (r"foobar", true),
+        (r"foobaz", true),
+        (r"foobarbaz", false),
+        (r"foo", false),
+    ];
+    let schema = json!({"type": "string", "pattern": "^foo(bar|baz)$"});
+
+    for (instance, accepted) in instance_accepted {
+        let value = format!("\"{}\"", instance);
+        check_schema_with_instance(&schema, &value, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Support the `pattern` keyword by compiling its regex into EBNF with shared-subrule extraction needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_pattern_repeated_group_extracted_to_shared_subrule() {
+    // A group reused multiple times under quantifiers (here `(ab)` appears as both a
+    // `{2}` repetition and a later optional occurrence) should be emitted once as a
+    // named rule and referenced, rather than inlined at every occurrence.
+    let schema_json = json!({"type": "string", "pattern": "^(ab){2}(ab)?$"}).to_string();
+    let ebnf = json_schema_to_ebnf(&schema_json, true, None, None, true, None);
+
+    let inline_fragment_occurrences = ebnf.matches(r#""a" "b""#).count();
+    assert!(
+        inline_fragment_occurrences <= 1,
+        "expected the repeated `(ab)` fragment to be factored into a shared subrule, \
+         got EBNF with {inline_fragment_occurrences} inline occurrences:\n{ebnf}"
+    );
+
+    let schema = json!({"type": "string", "pattern": "^(ab){2}(ab)?$"});
+    let instance_accepted = [
+        (r"abab", true),
+        (r"ababab", true),
+        (r"ab", false),
+        (r"abababab", false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        let value = format!("\"{}\"", instance);
+        check_schema_with_instance(&schema, &value, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Support `$ref`/`$defs` resolution (including remote `$id` URLs) in structural-tag `json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_ref_defs_resolution() {
+    // `$ref` should resolve against `$defs` in the same document, including refs nested
+    // several levels deep, so the referenced schema's constraints apply at the use site.
+    let schema = json!({
+        "type": "object",
+        "properties": {"inner": {"$ref": "#/$defs/Inner"}},
+        "required": ["inner"],
+        "$defs": {
+            "Inner": {"type": "object", "properties": {"value": {"type": "integer"}}, "required": ["value"]}
+        }
+    });
+
+    let instance_accepted = [
+        (r#"{"inner": {"value": 1}}"#, true),
+        (r#"{"inner": {"value": "not an int"}}"#, false),
+        (r#"{"inner": {}}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Resolve `$ref`, `$defs`, and remote schema references in `Grammar::from_json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_ref_resolves_against_legacy_definitions_keyword() {
+    // Draft-04 through draft-07 schemas spell the definitions bag `definitions` rather
+    // than `$defs`; `$ref` must resolve against either.
+    let schema = json!({
+        "type": "object",
+        "properties": {"inner": {"$ref": "#/definitions/Inner"}},
+        "required": ["inner"],
+        "definitions": {
+            "Inner": {"type": "string"}
+        }
+    });
+
+    let instance_accepted = [
+        (r#"{"inner": "x"}"#, true),
+        (r#"{"inner": 1}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Resolve `$ref`, `$defs`, and remote schema references in `Grammar::from_json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_recursive_ref_produces_a_self_referential_rule() {
+    // A tree-shaped schema refers to itself through `$ref`; the converter must emit a
+    // recursive EBNF rule rather than inlining the definition forever (which would not
+    // terminate).
+    let schema = json!({
+        "$ref": "#/$defs/Tree",
+        "$defs": {
+            "Tree": {
+                "type": "object",
+                "properties": {
+                    "value": {"type": "integer"},
+                    "children": {"type": "array", "items": {"$ref": "#/$defs/Tree"}}
+                },
+                "required": ["value", "children"]
+            }
+        }
+    });
+
+    let instance_accepted = [
+        (r#"{"value": 1, "children": []}"#, true),
+        (
+            r#"{"value": 1, "children": [{"value": 2, "children": []}]}"#,
+            true,
+        ),
+        (
+            r#"{"value": 1, "children": [{"value": "not an int", "children": []}]}"#,
+            false,
+        ),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Resolve `$ref`, `$defs`, and remote schema references in `Grammar::from_json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_shared_def_referenced_twice_compiles_to_one_rule() {
+    // Two properties referencing the same `$defs` entry should share a single compiled
+    // rule rather than duplicating the subschema; this is observable as both properties
+    // accepting/rejecting in lockstep with the shared definition's constraints.
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "a": {"$ref": "#/$defs/Id"},
+            "b": {"$ref": "#/$defs/Id"}
+        },
+        "required": ["a", "b"],
+        "$defs": {
+            "Id": {"type": "string", "minLength": 3}
+        }
+    });
+
+    let instance_accepted = [
+        (r#"{"a": "abc", "b": "xyz"}"#, true),
+        (r#"{"a": "ab", "b": "xyz"}"#, false),
+        (r#"{"a": "abc", "b": "xy"}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Tuple typing and `prefixItems`/`additionalItems` for array schemas needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_prefix_items_tuple() {
+    // `prefixItems` pins the type of each positional element; `items` controls whether
+    // (and how) further elements beyond the prefix are allowed.
+    let schema = json!({
+        "type": "array",
+        "prefixItems": [{"type": "string"}, {"type": "integer"}],
+        "items": false
+    });
+
+    let instance_accepted = [
+        (r#"["a", 1]"#, true),
+        (r#"["a", 1, 2]"#, false),
+        (r#"["a"]"#, false),
+        (r#"[1, "a"]"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "`allOf` merging and nested `anyOf`/`oneOf` in `json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_all_of_merging_with_nested_any_of() {
+    // `allOf` merges every branch's constraints into a single schema; when a branch is
+    // itself an `anyOf`/`oneOf`, the merge distributes over each alternative.
+    let schema = json!({
+        "allOf": [
+            {"type": "object", "properties": {"a": {"type": "string"}}, "required": ["a"]},
+            {"anyOf": [
+                {"properties": {"b": {"type": "integer"}}, "required": ["b"]},
+                {"properties": {"b": {"type": "boolean"}}, "required": ["b"]}
+            ]}
+        ]
+    });
+
+    let instance_accepted = [
+        (r#"{"a": "x", "b": 1}"#, true),
+        (r#"{"a": "x", "b": true}"#, true),
+        (r#"{"a": "x", "b": "nope"}"#, false),
+        (r#"{"b": 1}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Implement `allOf` as schema intersection in the JSON-schema frontend needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_all_of_merges_object_properties_and_required() {
+    // Merging must union `required` and recursively intersect per-key schemas rather
+    // than letting one branch's properties shadow the other's.
+    let schema = json!({
+        "allOf": [
+            {"type": "object", "properties": {"a": {"type": "string"}}, "required": ["a"]},
+            {"type": "object", "properties": {"b": {"type": "integer"}}, "required": ["b"]}
+        ]
+    });
+
+    let instance_accepted = [
+        (r#"{"a": "x", "b": 1}"#, true),
+        (r#"{"a": "x"}"#, false),
+        (r#"{"b": 1}"#, false),
+    ];
+    for (instance, accepted) in instance_accepted {
+        check_schema_with_instance(&schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Implement `allOf` as schema intersection in the JSON-schema frontend needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_all_of_intersects_string_length_and_numeric_bounds() {
+    let string_schema = json!({
+        "allOf": [
+            {"type": "string", "minLength": 2, "maxLength": 10},
+            {"type": "string", "minLength": 5, "maxLength": 8}
+        ]
+    });
+    // The intersection must take the max of minLength (5) and the min of maxLength (8).
+    let string_instance_accepted = [
+        (r#""abcd""#, false),
+        (r#""abcde""#, true),
+        (r#""abcdefgh""#, true),
+        (r#""abcdefghi""#, false),
+    ];
+    for (instance, accepted) in string_instance_accepted {
+        check_schema_with_instance(&string_schema, instance, accepted, true, None, None, true);
+    }
+
+    let numeric_schema = json!({
+        "allOf": [
+            {"type": "integer", "minimum": 0, "maximum": 100},
+            {"type": "integer", "minimum": 50, "maximum": 75}
+        ]
+    });
+    let numeric_instance_accepted = [
+        (r#"49"#, false),
+        (r#"60"#, true),
+        (r#"75"#, true),
+        (r#"76"#, false),
+    ];
+    for (instance, accepted) in numeric_instance_accepted {
+        check_schema_with_instance(&numeric_schema, instance, accepted, true, None, None, true);
+    }
+}
+
+#[test]
+#[ignore = "Implement `allOf` as schema intersection in the JSON-schema frontend needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_all_of_incompatible_types_errors() {
+    let schema = json!({
+        "allOf": [
+            {"type": "string"},
+            {"type": "integer"}
+        ]
+    });
+    let schema_json = serde_json::to_string(&schema).expect("serialize schema");
+    let result = Grammar::from_json_schema(
+        &schema_json,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+    );
+
+    match result {
+        Ok(_) => panic!("expected error for allOf branches with incompatible types"),
+        Err(err) => assert!(
+            err.contains("allOf") || err.contains("incompatible"),
+            "expected an error describing the incompatible allOf types, got '{}'",
+            err
+        ),
+    }
+}
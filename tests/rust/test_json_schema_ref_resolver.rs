@@ -0,0 +1,134 @@
+mod test_utils;
+
+use serial_test::serial;
+use std::collections::HashMap;
+use test_utils::*;
+use xgrammar::{Grammar, SchemaRefResolver};
+
+/// A resolver backed by an in-memory map, standing in for a `file://` or `http(s)://`
+/// fetcher. Each external `$ref` URI resolves to the JSON text of the schema it names.
+struct MapResolver {
+    documents: HashMap<String, String>,
+}
+
+impl SchemaRefResolver for MapResolver {
+    fn resolve(&self, uri: &str) -> Result<String, String> {
+        self.documents
+            .get(uri)
+            .cloned()
+            .ok_or_else(|| format!("no document registered for ref '{}'", uri))
+    }
+}
+
+#[test]
+#[ignore = "Resolve `$ref`, `$defs`, and remote schema references in `Grammar::from_json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_external_ref_resolves_through_pluggable_resolver() {
+    let mut documents = HashMap::new();
+    documents.insert(
+        "https://example.com/schemas/address.json".to_string(),
+        r#"{"type": "object", "properties": {"city": {"type": "string"}}, "required": ["city"]}"#
+            .to_string(),
+    );
+    let resolver = MapResolver { documents };
+
+    let schema = r#"{
+        "type": "object",
+        "properties": {"address": {"$ref": "https://example.com/schemas/address.json"}},
+        "required": ["address"]
+    }"#;
+
+    let grammar = Grammar::from_json_schema_with_resolver(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+        &resolver,
+    )
+    .unwrap();
+
+    assert!(is_grammar_accept_string(
+        &grammar,
+        r#"{"address": {"city": "Springfield"}}"#
+    ));
+    assert!(!is_grammar_accept_string(&grammar, r#"{"address": {}}"#));
+}
+
+#[test]
+#[ignore = "Resolve `$ref`, `$defs`, and remote schema references in `Grammar::from_json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_unresolvable_external_ref_surfaces_resolver_error() {
+    let resolver = MapResolver {
+        documents: HashMap::new(),
+    };
+
+    let schema = r#"{
+        "type": "object",
+        "properties": {"address": {"$ref": "https://example.com/schemas/missing.json"}},
+        "required": ["address"]
+    }"#;
+
+    let result = Grammar::from_json_schema_with_resolver(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+        &resolver,
+    );
+
+    match result {
+        Ok(_) => panic!("expected an error for an unresolvable external $ref"),
+        Err(err) => assert!(
+            err.contains("missing.json"),
+            "expected the resolver's error to surface, got '{}'",
+            err
+        ),
+    }
+}
+
+#[test]
+#[ignore = "Resolve `$ref`, `$defs`, and remote schema references in `Grammar::from_json_schema` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_resolved_external_subschema_is_cached_by_canonical_uri() {
+    // Two properties referencing the same external URI should only invoke the resolver's
+    // document once and compile to a single shared rule; we can't observe call counts
+    // directly on the trait object in this test, but we can confirm both properties are
+    // governed consistently by the single resolved definition.
+    let mut documents = HashMap::new();
+    documents.insert(
+        "https://example.com/schemas/id.json".to_string(),
+        r#"{"type": "string", "minLength": 2}"#.to_string(),
+    );
+    let resolver = MapResolver { documents };
+
+    let schema = r#"{
+        "type": "object",
+        "properties": {
+            "a": {"$ref": "https://example.com/schemas/id.json"},
+            "b": {"$ref": "https://example.com/schemas/id.json"}
+        },
+        "required": ["a", "b"]
+    }"#;
+
+    let grammar = Grammar::from_json_schema_with_resolver(
+        schema,
+        true,
+        None,
+        None::<(&str, &str)>,
+        true,
+        None,
+        false,
+        &resolver,
+    )
+    .unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, r#"{"a": "ab", "b": "cd"}"#));
+    assert!(!is_grammar_accept_string(&grammar, r#"{"a": "a", "b": "cd"}"#));
+    assert!(!is_grammar_accept_string(&grammar, r#"{"a": "ab", "b": "c"}"#));
+}
@@ -0,0 +1,104 @@
+mod test_utils;
+
+use xgrammar::{Grammar, GrammarCompiler, GrammarMatcher, TokenizerInfo, VocabType};
+
+fn matcher_from_grammar(grammar: &Grammar) -> GrammarMatcher {
+    let empty_vocab: Vec<&str> = vec![];
+    let stop_ids: Option<Box<[i32]>> = None;
+    let tokenizer_info =
+        TokenizerInfo::new(&empty_vocab, VocabType::RAW, &stop_ids, false).unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(grammar).unwrap();
+    GrammarMatcher::new(&compiled, None, true, -1).unwrap()
+}
+
+fn is_grammar_accept_string(grammar: &Grammar, input: &str) -> bool {
+    let mut matcher = matcher_from_grammar(grammar);
+    if !matcher.accept_string(input, false) {
+        return false;
+    }
+    matcher.is_terminated()
+}
+
+// A rule that reduces to a union of fixed literal strings is the shape the Aho-Corasick
+// acceleration targets (enum value lists, keyword sets, JSON field names). These tests
+// only observe accept/reject behavior, since the automaton is an internal speedup and
+// must not change grammar semantics.
+const KEYWORD_GRAMMAR: &str = r#"root ::= "true" | "false" | "null" | "nullable""#;
+
+#[test]
+#[ignore = "Aho-Corasick acceleration for literal-alternation grammars needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_literal_alternation_exact_matches() {
+    for literal in ["true", "false", "null", "nullable"] {
+        assert!(
+            is_grammar_accept_string(
+                &Grammar::from_ebnf(KEYWORD_GRAMMAR, "root").unwrap(),
+                literal
+            ),
+            "{literal} should be accepted"
+        );
+    }
+}
+
+#[test]
+#[ignore = "Aho-Corasick acceleration for literal-alternation grammars needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_literal_alternation_rejects_non_member() {
+    assert!(!is_grammar_accept_string(
+        &Grammar::from_ebnf(KEYWORD_GRAMMAR, "root").unwrap(),
+        "nul"
+    ));
+    assert!(!is_grammar_accept_string(
+        &Grammar::from_ebnf(KEYWORD_GRAMMAR, "root").unwrap(),
+        "truee"
+    ));
+    assert!(!is_grammar_accept_string(
+        &Grammar::from_ebnf(KEYWORD_GRAMMAR, "root").unwrap(),
+        "nullables"
+    ));
+}
+
+#[test]
+#[ignore = "Aho-Corasick acceleration for literal-alternation grammars needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_literal_alternation_shared_prefix_disambiguates_byte_by_byte() {
+    // "null" is a proper prefix of "nullable": after "null" the match can already end,
+    // and the automaton must stay live so that "able" still extends it to "nullable".
+    let grammar = Grammar::from_ebnf(KEYWORD_GRAMMAR, "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar);
+    assert!(matcher.accept_string("null", false));
+    assert!(matcher.is_terminated());
+    assert!(matcher.accept_string("able", false));
+    assert!(matcher.is_terminated());
+}
+
+#[test]
+#[ignore = "Aho-Corasick acceleration for literal-alternation grammars needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_literal_alternation_within_larger_grammar() {
+    // The union of literals can appear nested inside a non-trivial surrounding rule;
+    // the acceleration must stay transparent to composition with other rules.
+    let grammar_str = r#"root ::= "{" "\"kind\":" value "}"
+value ::= "true" | "false" | "null"
+"#;
+    let grammar = Grammar::from_ebnf(grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(
+        &grammar,
+        r#"{"kind":true}"#
+    ));
+    assert!(!is_grammar_accept_string(
+        &grammar,
+        r#"{"kind":maybe}"#
+    ));
+}
+
+#[test]
+#[ignore = "Aho-Corasick acceleration for literal-alternation grammars needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+fn test_large_literal_union_compiles_and_matches() {
+    // A sizeable enum-like union of fixed strings, the pattern the request calls out
+    // (enum value lists, keyword sets, JSON field names).
+    let literals: Vec<String> = (0..64).map(|i| format!("\"ENUM_VALUE_{i}\"")).collect();
+    let grammar_str = format!("root ::= {}", literals.join(" | "));
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+
+    assert!(is_grammar_accept_string(&grammar, "ENUM_VALUE_0"));
+    assert!(is_grammar_accept_string(&grammar, "ENUM_VALUE_63"));
+    assert!(!is_grammar_accept_string(&grammar, "ENUM_VALUE_64"));
+}
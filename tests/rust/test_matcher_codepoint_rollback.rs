@@ -0,0 +1,107 @@
+mod test_utils;
+
+use serial_test::serial;
+use xgrammar::{Grammar, GrammarCompiler, GrammarMatcher, TokenizerInfo, VocabType};
+
+fn matcher_from_grammar(grammar: &Grammar) -> GrammarMatcher {
+    let empty_vocab: Vec<&str> = vec![];
+    let stop_ids: Option<Box<[i32]>> = None;
+    let tokenizer_info =
+        TokenizerInfo::new(&empty_vocab, VocabType::RAW, &stop_ids, false).unwrap();
+    let mut compiler = GrammarCompiler::new(&tokenizer_info, 1, false, -1).unwrap();
+    let compiled = compiler.compile_grammar(grammar).unwrap();
+    GrammarMatcher::new(&compiled, None, true, -1).unwrap()
+}
+
+// `accept_codepoint` drives the same stack set `accept_string` does, but one codepoint at
+// a time, and `snapshot`/`rollback` let a caller probe a speculative continuation and
+// cheaply undo it without rebuilding the matcher.
+
+#[test]
+#[ignore = "Incremental per-codepoint acceptance API with rollback, exposed alongside `is_grammar_accept_string` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_accept_codepoint_matches_accept_string_byte_by_byte() {
+    let grammar = Grammar::from_ebnf(r#"root ::= "abc""#, "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar);
+
+    assert!(matcher.accept_codepoint('a'));
+    assert!(matcher.accept_codepoint('b'));
+    assert!(matcher.accept_codepoint('c'));
+    assert!(matcher.is_terminated());
+}
+
+#[test]
+#[ignore = "Incremental per-codepoint acceptance API with rollback, exposed alongside `is_grammar_accept_string` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_accept_codepoint_rejects_a_disallowed_continuation() {
+    let grammar = Grammar::from_ebnf(r#"root ::= "abc""#, "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar);
+
+    assert!(matcher.accept_codepoint('a'));
+    assert!(!matcher.accept_codepoint('z'));
+}
+
+#[test]
+#[ignore = "Incremental per-codepoint acceptance API with rollback, exposed alongside `is_grammar_accept_string` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_accept_codepoint_handles_multi_byte_utf8() {
+    let grammar = Grammar::from_ebnf(r#"root ::= "好" "的""#, "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar);
+
+    assert!(matcher.accept_codepoint('好'));
+    assert!(matcher.accept_codepoint('的'));
+    assert!(matcher.is_terminated());
+}
+
+#[test]
+#[ignore = "Incremental per-codepoint acceptance API with rollback, exposed alongside `is_grammar_accept_string` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_snapshot_rollback_undoes_a_speculative_probe() {
+    let grammar = Grammar::from_ebnf(r#"root ::= "ab" | "ac""#, "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar);
+
+    assert!(matcher.accept_codepoint('a'));
+    let snapshot = matcher.snapshot();
+    assert!(!matcher.can_reach_end());
+
+    // Speculatively probe 'b', then roll back as though the candidate were rejected.
+    assert!(matcher.accept_codepoint('b'));
+    matcher.rollback(snapshot);
+
+    // The rollback must restore the pre-probe stack set: 'c' is live again.
+    assert!(matcher.accept_codepoint('c'));
+    assert!(matcher.is_terminated());
+}
+
+#[test]
+#[ignore = "Incremental per-codepoint acceptance API with rollback, exposed alongside `is_grammar_accept_string` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_can_reach_end_is_false_mid_mandatory_literal() {
+    let grammar = Grammar::from_ebnf(r#"root ::= "abc""#, "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar);
+
+    assert!(matcher.accept_codepoint('a'));
+    assert!(!matcher.can_reach_end());
+
+    assert!(matcher.accept_codepoint('b'));
+    assert!(matcher.accept_codepoint('c'));
+    assert!(matcher.can_reach_end());
+}
+
+#[test]
+#[ignore = "Incremental per-codepoint acceptance API with rollback, exposed alongside `is_grammar_accept_string` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_rollback_to_initial_snapshot_resets_the_whole_match() {
+    let grammar = Grammar::from_ebnf(r#"root ::= "abc""#, "root").unwrap();
+    let mut matcher = matcher_from_grammar(&grammar);
+    let initial = matcher.snapshot();
+
+    assert!(matcher.accept_codepoint('a'));
+    assert!(matcher.accept_codepoint('b'));
+    matcher.rollback(initial);
+
+    assert!(matcher.accept_codepoint('a'));
+    assert!(matcher.accept_codepoint('b'));
+    assert!(matcher.accept_codepoint('c'));
+    assert!(matcher.is_terminated());
+}
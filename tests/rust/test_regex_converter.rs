@@ -84,6 +84,44 @@ fn test_escaped_char_class() {
     assert!(is_grammar_accept_string(&grammar, instance));
 }
 
+#[test]
+#[ignore = "Add Unicode property escapes `\p{...}` / `\P{...}` to the regex frontend needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_unicode_property_escapes() {
+    // `\p{...}` matches a Unicode general category or script; `\P{...}` and `\p{^...}` negate it.
+    let regex = r"\p{Lu}\p{Nd}";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "A5"));
+    assert!(!is_grammar_accept_string(&grammar, "a5"));
+    assert!(!is_grammar_accept_string(&grammar, "AA"));
+
+    let regex = r"\p{Greek}+";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "αβγ"));
+    assert!(!is_grammar_accept_string(&grammar, "abc"));
+
+    let regex = r"\P{L}";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "5"));
+    assert!(!is_grammar_accept_string(&grammar, "a"));
+
+    let regex = r"\p{^L}";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "5"));
+    assert!(!is_grammar_accept_string(&grammar, "a"));
+
+    // Property escapes are also usable inside a bracketed character class.
+    let regex = r"[\p{L}0-9]+";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "a1B2"));
+    assert!(!is_grammar_accept_string(&grammar, "a!"));
+}
+
 #[test]
 #[serial]
 fn test_char_class() {
@@ -96,6 +134,25 @@ fn test_char_class() {
     assert!(is_grammar_accept_string(&grammar, instance));
 }
 
+#[test]
+#[ignore = "Character-class set operations (intersection and nested negated classes) needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_char_class_intersection() {
+    // `&&` intersects union terms; a term may itself be a nested bracketed class.
+    let regex = "[a-z&&[^aeiou]]+";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "bcd"));
+    assert!(!is_grammar_accept_string(&grammar, "aei"));
+    assert!(!is_grammar_accept_string(&grammar, "BCD"));
+
+    let regex = r"[\p{L}&&\p{ASCII}]+";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "abcXYZ"));
+    assert!(!is_grammar_accept_string(&grammar, "α"));
+}
+
 #[test]
 #[serial]
 fn test_boundary() {
@@ -108,6 +165,51 @@ fn test_boundary() {
     assert!(is_grammar_accept_string(&grammar, instance));
 }
 
+#[test]
+#[ignore = "Support `\A`, `\z`, `\Z` and word-boundary anchors in `regex_to_ebnf` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_string_anchors() {
+    // Since xgrammar always matches the entire generated string, `\A`/`\z` behave
+    // exactly like `^`/`$`, and `\Z` additionally tolerates one trailing newline.
+    let regex = r"\Aabc\z";
+    let instance = "abc";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let expected_grammar = "root ::= \"a\" \"b\" \"c\"\n";
+    assert_eq!(grammar_str, expected_grammar);
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, instance));
+
+    let regex = r"\Aabc\Z";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "abc"));
+    assert!(is_grammar_accept_string(&grammar, "abc\n"));
+    assert!(!is_grammar_accept_string(&grammar, "abc\n\n"));
+}
+
+#[test]
+#[ignore = "Support `\A`, `\z`, `\Z` and word-boundary anchors in `regex_to_ebnf` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_word_boundary() {
+    // `\b` asserts a word/non-word transition; adjacent to a literal whose word status
+    // is statically known, it lowers to a no-op instead of requiring full context.
+    let regex = r"\bcat\b";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let expected_grammar = "root ::= \"c\" \"a\" \"t\"\n";
+    assert_eq!(grammar_str, expected_grammar);
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "cat"));
+
+    // `\B` (non-boundary) between two literal word characters is also a statically
+    // known no-op.
+    let regex = r"foo\Bbar";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let expected_grammar = "root ::= \"f\" \"o\" \"o\" \"b\" \"a\" \"r\"\n";
+    assert_eq!(grammar_str, expected_grammar);
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "foobar"));
+}
+
 #[test]
 #[serial]
 fn test_disjunction() {
@@ -153,7 +255,7 @@ fn test_quantifier() {
 fn test_consecutive_quantifiers() {
     let bad = ["a{1,3}?{1,3}", "a???", "a++", "a+?{1,3}"];
     for regex in bad {
-        let err = testing::regex_to_ebnf(regex, true).unwrap_err();
+        let err = testing::regex_to_ebnf(regex, true).unwrap_err().to_string();
         assert!(
             err.contains("Two consecutive repetition modifiers are not allowed."),
             "unexpected error for {regex}: {err}"
@@ -299,7 +401,7 @@ fn test_email() {
 #[test]
 #[serial]
 fn test_empty_character_class() {
-    let err = testing::regex_to_ebnf("[]", true).unwrap_err();
+    let err = testing::regex_to_ebnf("[]", true).unwrap_err().to_string();
     assert!(
         err.contains("Empty character class is not allowed in regex."),
         "unexpected error: {err}"
@@ -331,23 +433,90 @@ fn test_group_modifiers() {
         ("(?!abc)", "Lookahead is not supported yet."),  // Negative lookahead
         ("(?<=abc)", "Lookbehind is not supported yet."), // Positive lookbehind
         ("(?<!abc)", "Lookbehind is not supported yet."), // Negative lookbehind
-        ("(?i)abc", "Group modifier flag is not supported yet."), // Case-insensitive flag
     ];
 
     for (regex, expected) in unsupported {
-        let err = testing::regex_to_ebnf(regex, true).unwrap_err();
+        let err = testing::regex_to_ebnf(regex, true).unwrap_err().to_string();
         assert!(err.contains(expected), "regex={regex}, err={err}");
     }
 }
 
+#[test]
+#[ignore = "Support case-insensitive matching via `(?i)` and scoped `(?i:...)` flags in `regex_to_ebnf` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_case_insensitive_flag() {
+    // `(?i)` folds case for the remainder of the current group scope.
+    let regex = "(?i)abc";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let expected_grammar = "root ::= [aA] [bB] [cC]\n";
+    assert_eq!(grammar_str, expected_grammar);
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "abc"));
+    assert!(is_grammar_accept_string(&grammar, "ABC"));
+    assert!(is_grammar_accept_string(&grammar, "AbC"));
+
+    // `(?-i)` turns folding back off for the rest of the scope.
+    let regex = "(?i)ab(?-i)cd";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let expected_grammar = "root ::= [aA] [bB] \"c\" \"d\"\n";
+    assert_eq!(grammar_str, expected_grammar);
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "ABcd"));
+    assert!(!is_grammar_accept_string(&grammar, "ABCD"));
+
+    // `(?i:...)` scopes the fold to the inner group only.
+    let regex = "foo(?i:bar)baz";
+    let grammar_str = testing::regex_to_ebnf(regex, true).unwrap();
+    let expected_grammar =
+        "root ::= \"f\" \"o\" \"o\" ( [bB] [aA] [rR] ) \"b\" \"a\" \"z\"\n";
+    assert_eq!(grammar_str, expected_grammar);
+    let grammar = Grammar::from_ebnf(&grammar_str, "root").unwrap();
+    assert!(is_grammar_accept_string(&grammar, "fooBARbaz"));
+    assert!(!is_grammar_accept_string(&grammar, "FOObarbaz"));
+}
+
+#[test]
+#[ignore = "Structured, positioned error type for regex and EBNF compilation instead of `String` needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_regex_compile_error_is_structured() {
+    // `regex_to_ebnf` surfaces a typed `RegexCompileError` with a byte offset into the
+    // source pattern and the offending fragment, not just a flat message string. Its
+    // `Display` impl reproduces the same text the old `Result<_, String>` callers matched on.
+    let err = testing::regex_to_ebnf("a(b", true).unwrap_err();
+    assert!(matches!(err, xgrammar::RegexCompileError::UnmatchedParen { .. }));
+    assert_eq!(err.offset(), 1);
+    assert_eq!(err.fragment(), "(");
+    assert!(err.to_string().contains("Unmatched parenthesis"));
+
+    let err = testing::regex_to_ebnf("[]", true).unwrap_err();
+    assert!(matches!(err, xgrammar::RegexCompileError::EmptyCharClass { .. }));
+    assert!(
+        err.to_string()
+            .contains("Empty character class is not allowed in regex.")
+    );
+
+    let err = testing::regex_to_ebnf("(?=abc)", true).unwrap_err();
+    assert!(matches!(err, xgrammar::RegexCompileError::Lookaround { .. }));
+    assert!(err.to_string().contains("Lookahead is not supported yet."));
+
+    let err = testing::regex_to_ebnf("a???", true).unwrap_err();
+    assert!(matches!(
+        err,
+        xgrammar::RegexCompileError::ConsecutiveQuantifiers { .. }
+    ));
+    assert_eq!(err.offset(), 2);
+}
+
 /// Test unmatched parentheses errors
 #[test]
 #[serial]
 fn test_unmatched_parentheses() {
-    let err = testing::regex_to_ebnf("abc)", true).unwrap_err();
+    let err = testing::regex_to_ebnf("abc)", true).unwrap_err().to_string();
     assert!(err.contains("Unmatched ')'"), "unexpected error: {err}");
 
-    let err = testing::regex_to_ebnf("abc((a)", true).unwrap_err();
+    let err = testing::regex_to_ebnf("abc((a)", true)
+        .unwrap_err()
+        .to_string();
     assert!(
         err.contains("The parenthesis is not closed."),
         "unexpected error: {err}"
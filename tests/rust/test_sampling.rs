@@ -0,0 +1,104 @@
+use xgrammar::sampling::{apply_top_p, batch_sample, masked_log_softmax, sample_index};
+
+const VOCAB_SIZE: usize = 5;
+
+fn bitmask_allowing(allowed: &[usize]) -> [i32; 1] {
+    let mut word = 0u32;
+    for &i in allowed {
+        word |= 1 << i;
+    }
+    [word as i32]
+}
+
+#[test]
+fn test_masked_log_softmax_restricts_to_allowed_tokens() {
+    let mut logits = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    let bitmask = bitmask_allowing(&[1, 3]);
+    masked_log_softmax(&mut logits, &bitmask, VOCAB_SIZE, 1.0).unwrap();
+
+    assert_eq!(logits[0], f32::NEG_INFINITY);
+    assert_eq!(logits[2], f32::NEG_INFINITY);
+    assert_eq!(logits[4], f32::NEG_INFINITY);
+
+    // The surviving log-probabilities must exponentiate to a distribution that sums to 1.
+    let total: f32 = [logits[1], logits[3]].iter().map(|lp| lp.exp()).sum();
+    assert!((total - 1.0).abs() < 1e-5, "total = {total}");
+    // Token 3 has the higher raw logit, so it must end up with the higher probability.
+    assert!(logits[3] > logits[1]);
+}
+
+#[test]
+fn test_masked_log_softmax_single_allowed_token_short_circuits() {
+    let mut logits = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    let bitmask = bitmask_allowing(&[2]);
+    masked_log_softmax(&mut logits, &bitmask, VOCAB_SIZE, 1.0).unwrap();
+
+    assert_eq!(logits[2], 0.0);
+    for (i, &logit) in logits.iter().enumerate() {
+        if i != 2 {
+            assert_eq!(logit, f32::NEG_INFINITY);
+        }
+    }
+}
+
+#[test]
+fn test_masked_log_softmax_no_allowed_token_errors() {
+    let mut logits = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    let bitmask = [0i32];
+    assert!(masked_log_softmax(&mut logits, &bitmask, VOCAB_SIZE, 1.0).is_err());
+}
+
+#[test]
+fn test_apply_top_p_truncates_and_renormalizes() {
+    let mut logits = vec![1.0, 10.0, 2.0, 9.0, 0.5];
+    let bitmask = bitmask_allowing(&[0, 1, 2, 3, 4]);
+    masked_log_softmax(&mut logits, &bitmask, VOCAB_SIZE, 1.0).unwrap();
+    apply_top_p(&mut logits, 0.5).unwrap();
+
+    let surviving: Vec<usize> = (0..VOCAB_SIZE).filter(|&i| logits[i] != f32::NEG_INFINITY).collect();
+    // Tokens 1 and 3 dominate the mass (logits 10.0, 9.0), so a tight top_p should keep
+    // only (a prefix of) those two rather than every originally-allowed token.
+    assert!(surviving.len() < VOCAB_SIZE);
+    assert!(surviving.contains(&1));
+
+    let total: f32 = surviving.iter().map(|&i| logits[i].exp()).sum();
+    assert!((total - 1.0).abs() < 1e-5, "total = {total}");
+}
+
+#[test]
+fn test_sample_index_is_deterministic_for_a_given_seed() {
+    let mut logits = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    let bitmask = bitmask_allowing(&[0, 1, 2, 3, 4]);
+    masked_log_softmax(&mut logits, &bitmask, VOCAB_SIZE, 1.0).unwrap();
+
+    let a = sample_index(&logits, 42).unwrap();
+    let b = sample_index(&logits, 42).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_batch_sample_applies_shuffled_index_mapping() {
+    let batch_size = 2;
+    let mut logits = vec![0.0f32; batch_size * VOCAB_SIZE];
+    // Row 0: only token 1 allowed. Row 1: only token 3 allowed.
+    let bitmask = vec![bitmask_allowing(&[1])[0], bitmask_allowing(&[3])[0]];
+
+    // Output row i is read from logical row `indices[i]` (reversed here).
+    let indices = [1i32, 0i32];
+    let seeds = [1u64, 2u64];
+
+    let sampled = batch_sample(
+        &mut logits,
+        &bitmask,
+        batch_size,
+        VOCAB_SIZE,
+        1,
+        Some(&indices),
+        1.0,
+        1.0,
+        &seeds,
+    )
+    .unwrap();
+
+    assert_eq!(sampled, vec![3, 1]);
+}
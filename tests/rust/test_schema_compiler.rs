@@ -0,0 +1,102 @@
+mod test_utils;
+
+use serial_test::serial;
+use test_utils::*;
+use xgrammar::schema::{FieldType, RecordField, SchemaCompiler, TypeDef};
+
+#[test]
+#[serial]
+fn test_self_recursive_tree_schema() {
+    // A binary tree node: { "value": <int>, "left": <Node>?, "right": <Node>? }. "Node"
+    // references itself, so the compiler must emit it as a named rule referencing itself
+    // rather than trying to inline/expand it infinitely.
+    let mut compiler = SchemaCompiler::new();
+    compiler.define_type(
+        "Node",
+        TypeDef::Record(vec![
+            RecordField::required("value", FieldType::Int),
+            RecordField::optional("left", FieldType::Ref("Node".into())),
+            RecordField::optional("right", FieldType::Ref("Node".into())),
+        ]),
+    );
+
+    let grammar = compiler
+        .compile("Node")
+        .expect("self-recursive schema should compile to a recursive grammar rule");
+
+    assert!(is_grammar_accept_string(
+        &grammar,
+        r#"{"value": 1, "left": {"value": 2}, "right": null}"#
+    ));
+    assert!(!is_grammar_accept_string(
+        &grammar,
+        r#"{"value": 1, "left": {"value": "not an int"}}"#
+    ));
+}
+
+#[test]
+#[serial]
+fn test_mutually_recursive_schema_scc() {
+    // "Expr" and "Stmt" reference each other; both must end up in the same strongly
+    // connected component and be emitted as named rules rather than one being inlined
+    // into a non-terminating expansion of the other.
+    let mut compiler = SchemaCompiler::new();
+    compiler.define_type(
+        "Expr",
+        TypeDef::Alternation(vec![FieldType::Int, FieldType::Ref("Stmt".into())]),
+    );
+    compiler.define_type(
+        "Stmt",
+        TypeDef::Record(vec![RecordField::required("cond", FieldType::Ref("Expr".into()))]),
+    );
+
+    let grammar = compiler
+        .compile("Stmt")
+        .expect("mutually recursive schema should compile");
+
+    assert!(is_grammar_accept_string(&grammar, r#"{"cond": 1}"#));
+    assert!(is_grammar_accept_string(&grammar, r#"{"cond": {"cond": 1}}"#));
+}
+
+#[test]
+#[serial]
+fn test_non_recursive_single_use_type_is_inlined() {
+    // "Address" is only referenced once, by "Person", and never participates in a cycle,
+    // so the compiler is free to inline it directly into "Person" instead of emitting a
+    // separate named rule; either way the accepted language must be the same.
+    let mut compiler = SchemaCompiler::new();
+    compiler.define_type(
+        "Address",
+        TypeDef::Record(vec![RecordField::required("city", FieldType::String)]),
+    );
+    compiler.define_type(
+        "Person",
+        TypeDef::Record(vec![
+            RecordField::required("name", FieldType::String),
+            RecordField::required("address", FieldType::Ref("Address".into())),
+        ]),
+    );
+
+    let grammar = compiler.compile("Person").expect("non-recursive schema should compile");
+
+    assert!(is_grammar_accept_string(
+        &grammar,
+        r#"{"name": "Ada", "address": {"city": "London"}}"#
+    ));
+    assert!(!is_grammar_accept_string(&grammar, r#"{"name": "Ada"}"#));
+}
+
+#[test]
+#[serial]
+fn test_unknown_type_reference_is_an_error() {
+    let mut compiler = SchemaCompiler::new();
+    compiler.define_type(
+        "Leaf",
+        TypeDef::Record(vec![RecordField::required(
+            "parent",
+            FieldType::Ref("DoesNotExist".into()),
+        )]),
+    );
+
+    assert!(compiler.compile("Leaf").is_err());
+}
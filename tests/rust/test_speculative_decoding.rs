@@ -124,4 +124,112 @@ fn test_traverse_draft_tree_shape_assertion() {
     assert!(testing::traverse_draft_tree(&rt, &rs_wrong_dtype, &dt_wrong_dtype, &mut matcher, &mut bitmask_tensor).is_err());
 }
 
+// `fill_draft_tree_bitmask` is the public counterpart of `testing::traverse_draft_tree`:
+// same DLTensor-driven tree traversal, but with typed errors on the public path instead of
+// test-only `Check failed` panics, and a documented guarantee that matcher state is restored
+// after the call so a caller can immediately reuse the matcher for the next decoding step.
+
+#[test]
+#[serial]
+fn test_fill_draft_tree_bitmask_matches_testing_traverse_draft_tree() {
+    let grammar = Grammar::builtin_json_grammar();
+    let vocab = ["a", "b", "c", "{", "}", "\"", ":", ",", " ", "true", "false", "null"];
+    let tok = TokenizerInfo::new(&vocab, VocabType::RAW, &None, false).unwrap();
+    let mut compiler = GrammarCompiler::new(&tok, 1, false, -1).unwrap();
+    let compiled_grammar = compiler.compile_grammar(&grammar).unwrap();
+    let mut matcher = GrammarMatcher::new(&compiled_grammar, None, true, -1).unwrap();
+
+    let num_nodes = 3usize;
+    let mut retrieve_next_token: Vec<i64> = vec![1, 2, -1];
+    let mut retrieve_next_sibling: Vec<i64> = vec![-1, -1, -1];
+    let mut draft_tokens: Vec<i64> = vec![3, 6, 4]; // {, :, }
+
+    let (rt, _rt_shape, _rt_strides) = create_i64_1d_dltensor(&mut retrieve_next_token);
+    let (rs, _rs_shape, _rs_strides) = create_i64_1d_dltensor(&mut retrieve_next_sibling);
+    let (dt, _dt_shape, _dt_strides) = create_i64_1d_dltensor(&mut draft_tokens);
+
+    let vocab_size = vocab.len();
+    let mut bitmask_data = allocate_token_bitmask(num_nodes, vocab_size);
+    let (mut bitmask_tensor, _bshape, _bstrides) =
+        create_bitmask_dltensor(&mut bitmask_data, num_nodes, vocab_size);
+
+    matcher
+        .fill_draft_tree_bitmask(&rt, &rs, &dt, &mut bitmask_tensor)
+        .unwrap();
+
+    let rejected = testing::get_masked_tokens_from_bitmask(&bitmask_tensor, vocab_size as i32, 0);
+    assert!(!rejected.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_fill_draft_tree_bitmask_reports_typed_errors_for_bad_tensors() {
+    let grammar = Grammar::builtin_json_grammar();
+    let vocab = ["a", "b", "c", "{", "}", "\"", ":", ",", " ", "true", "false", "null"];
+    let tok = TokenizerInfo::new(&vocab, VocabType::RAW, &None, false).unwrap();
+    let mut compiler = GrammarCompiler::new(&tok, 1, false, -1).unwrap();
+    let compiled_grammar = compiler.compile_grammar(&grammar).unwrap();
+    let mut matcher = GrammarMatcher::new(&compiled_grammar, None, true, -1).unwrap();
+
+    let mut retrieve_next_token: Vec<i64> = vec![1, 2, -1];
+    let mut retrieve_next_sibling_wrong_dtype: Vec<i32> = vec![-1, -1, -1];
+    let mut draft_tokens: Vec<i64> = vec![3, 6, 4];
+
+    let (rt, _rt_shape, _rt_strides) = create_i64_1d_dltensor(&mut retrieve_next_token);
+    let (rs_wrong_dtype, _rs_shape, _rs_strides) =
+        create_i32_1d_dltensor(&mut retrieve_next_sibling_wrong_dtype);
+    let (dt, _dt_shape, _dt_strides) = create_i64_1d_dltensor(&mut draft_tokens);
+
+    let vocab_size = vocab.len();
+    let mut bitmask_data = allocate_token_bitmask(3, vocab_size);
+    let (mut bitmask_tensor, _bshape, _bstrides) =
+        create_bitmask_dltensor(&mut bitmask_data, 3, vocab_size);
+
+    let err = matcher
+        .fill_draft_tree_bitmask(&rt, &rs_wrong_dtype, &dt, &mut bitmask_tensor)
+        .expect_err("expected a typed error for an i32 retrieve_next_sibling tensor");
+    assert!(
+        err.to_string().contains("retrieve_next_sibling") || err.to_string().contains("i64"),
+        "expected the error to name the offending tensor/dtype, got '{}'",
+        err
+    );
+}
+
+#[test]
+#[serial]
+fn test_fill_draft_tree_bitmask_restores_matcher_state_between_sibling_branches() {
+    // After traversing one branch of the draft tree, the matcher's own state (independent
+    // of the bitmasks written per-node) must be exactly as it was before the call, so a
+    // caller can traverse a sibling branch from the same shared parent state.
+    let grammar = Grammar::builtin_json_grammar();
+    let vocab = ["a", "b", "c", "{", "}", "\"", ":", ",", " ", "true", "false", "null"];
+    let tok = TokenizerInfo::new(&vocab, VocabType::RAW, &None, false).unwrap();
+    let mut compiler = GrammarCompiler::new(&tok, 1, false, -1).unwrap();
+    let compiled_grammar = compiler.compile_grammar(&grammar).unwrap();
+    let mut matcher = GrammarMatcher::new(&compiled_grammar, None, true, -1).unwrap();
+
+    assert!(matcher.accept_string("{", false));
+    let state_before = matcher.find_jump_forward_string();
+
+    let num_nodes = 2usize;
+    let mut retrieve_next_token: Vec<i64> = vec![1, -1];
+    let mut retrieve_next_sibling: Vec<i64> = vec![-1, -1];
+    let mut draft_tokens: Vec<i64> = vec![5, 6]; // ", :
+
+    let (rt, _rt_shape, _rt_strides) = create_i64_1d_dltensor(&mut retrieve_next_token);
+    let (rs, _rs_shape, _rs_strides) = create_i64_1d_dltensor(&mut retrieve_next_sibling);
+    let (dt, _dt_shape, _dt_strides) = create_i64_1d_dltensor(&mut draft_tokens);
+
+    let vocab_size = vocab.len();
+    let mut bitmask_data = allocate_token_bitmask(num_nodes, vocab_size);
+    let (mut bitmask_tensor, _bshape, _bstrides) =
+        create_bitmask_dltensor(&mut bitmask_data, num_nodes, vocab_size);
+
+    matcher
+        .fill_draft_tree_bitmask(&rt, &rs, &dt, &mut bitmask_tensor)
+        .unwrap();
+
+    assert_eq!(matcher.find_jump_forward_string(), state_before);
+}
+
 
@@ -416,3 +416,119 @@ fn test_vocab_conversion() {
         }
     }
 }
+
+#[test]
+#[ignore = "Add an RWKV/world trie-tokenizer vocab type to TokenizerInfo needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_rwkv_world_vocab_type() {
+    // RWKV's "world" tokenizer vocab is neither byte-level nor byte-fallback: tokens are
+    // raw UTF-8 bytes looked up through a trie, so RWKV_WORLD needs its own VocabType and
+    // decoding path.
+    let vocab = vec!["<s>", "hello", " world", "\u{e000}\u{e001}"];
+    let tokenizer_info =
+        xgrammar::TokenizerInfo::new(&vocab, xgrammar::VocabType::RWKV_WORLD, &None, false).unwrap();
+    let decoded = tokenizer_info.decoded_vocab();
+
+    assert_eq!(&*decoded[1], b"hello");
+    assert_eq!(&*decoded[2], b" world");
+}
+
+#[test]
+#[ignore = "Support WordPiece vocabularies (BERT/ProphetNet) in decoded_vocab needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_wordpiece_vocab_type() {
+    // WordPiece vocabularies (BERT/ProphetNet) mark word-continuation pieces with a
+    // "##" prefix instead of a leading-space marker; decoded_vocab must strip that
+    // marker while still distinguishing continuation pieces from word-initial ones.
+    let vocab = vec!["[CLS]", "hello", "##world", "##s"];
+    let tokenizer_info =
+        xgrammar::TokenizerInfo::new(&vocab, xgrammar::VocabType::WORD_PIECE, &None, false).unwrap();
+    let decoded = tokenizer_info.decoded_vocab();
+
+    assert_eq!(&*decoded[1], b"hello");
+    assert_eq!(&*decoded[2], b"world");
+    assert_eq!(&*decoded[3], b"s");
+}
+
+#[test]
+#[ignore = "Byte-level BPE vocabulary decoding in TokenizerInfo needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_byte_level_vocab_type() {
+    // GPT-2/Llama-3 style tokenizers store token strings through a reversible
+    // byte<->unicode table instead of raw bytes: space is "\u{0120}" (Ġ), newline is
+    // "\u{010a}" (Ċ), and printable ASCII maps to itself. VocabType::BYTE_LEVEL should
+    // invert that table so decoded_vocab yields the true byte sequence the model meant.
+    let vocab = vec!["<s>", "Ġhello", "helloĊworld", "Ā"];
+    let tokenizer_info =
+        xgrammar::TokenizerInfo::new(&vocab, xgrammar::VocabType::BYTE_LEVEL, &None, false)
+            .unwrap();
+    let decoded = tokenizer_info.decoded_vocab();
+
+    assert_eq!(&*decoded[1], b" hello");
+    assert_eq!(&*decoded[2], b"hello\nworld");
+    assert_eq!(&*decoded[3], &[0x00]);
+}
+
+#[test]
+#[ignore = "Byte-level BPE vocabulary decoding in TokenizerInfo needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_byte_level_vocab_rejects_malformed_token() {
+    // A byte-level token string containing a codepoint outside the 256-entry
+    // byte<->unicode table (e.g. an ordinary Unicode letter that was never produced by
+    // the encoding side) can't be mapped back to a byte and should be rejected rather
+    // than silently dropped or passed through.
+    let vocab = vec!["<s>", "helloλ"];
+    let result =
+        xgrammar::TokenizerInfo::new(&vocab, xgrammar::VocabType::BYTE_LEVEL, &None, false);
+    assert!(result.is_err());
+}
+
+#[test]
+#[serial]
+#[ignore = "requires network access to download a SentencePiece model with a precompiled_charsmap"]
+fn test_sentencepiece_precompiled_charsmap_normalization() {
+    // Some SentencePiece models ship a `precompiled_charsmap` that remaps characters
+    // (e.g. fullwidth -> halfwidth, NFKC-like folding) before tokenization; TokenizerInfo
+    // construction should apply it so decoded_vocab matches what the model actually saw.
+    let path = download_tokenizer_json("google/gemma-2b-it").expect("download tokenizer.json");
+    let tokenizer = tokenizers::Tokenizer::from_file(&path).expect("load tokenizer");
+    let tokenizer_info =
+        xgrammar::TokenizerInfo::from_huggingface(&tokenizer, None, None).unwrap();
+
+    // Only checks that construction succeeds on such a model; fullwidth and halfwidth
+    // acceptance are not compared here.
+    assert!(tokenizer_info.vocab_size() > 0);
+}
+
+#[test]
+#[ignore = "Track encoder-decoder metadata (decoder_start_token_id) for T5-style models needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_decoder_start_token_id_for_encoder_decoder_models() {
+    // T5-style encoder-decoder models feed the decoder a dedicated start token that is
+    // distinct from the usual BOS token; TokenizerInfo should track it separately so
+    // matchers seeded for decoding start in the right state.
+    let vocab = vec!["<pad>", "</s>", "<unk>", "hello"];
+    let tokenizer_info = xgrammar::TokenizerInfo::new(&vocab, xgrammar::VocabType::RAW, &None, false).unwrap();
+    let with_decoder_start = tokenizer_info.with_decoder_start_token_id(Some(0));
+
+    assert_eq!(with_decoder_start.decoder_start_token_id(), Some(0));
+    assert_eq!(tokenizer_info.decoder_start_token_id(), None);
+}
+
+#[test]
+#[ignore = "Structured special-token taxonomy instead of a flat special_token_ids set needs xgrammar's C++ engine; this checkout has no cpp/ or include/ tree to implement or bind it against (see rust/src/lib.rs)"]
+#[serial]
+fn test_structured_special_token_taxonomy() {
+    // special_token_ids used to be a flat set; special_tokens() should classify each one
+    // (bos/eos/pad/unknown/additional) so callers don't have to re-derive the role of
+    // each id from naming conventions.
+    let vocab = vec!["<s>", "</s>", "<pad>", "<unk>", "hello"];
+    let tokenizer_info = xgrammar::TokenizerInfo::new(&vocab, xgrammar::VocabType::RAW, &None, false).unwrap();
+    let special = tokenizer_info.special_tokens();
+
+    assert_eq!(special.bos_token_id(), Some(0));
+    assert_eq!(special.eos_token_id(), Some(1));
+    assert_eq!(special.pad_token_id(), Some(2));
+    assert!(special.ids().contains(&3));
+    assert!(!special.ids().contains(&4));
+}
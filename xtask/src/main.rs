@@ -0,0 +1,45 @@
+//! Developer tasks for the xgrammar-rs crate, in the style of rust-analyzer's xtask.
+//!
+//! `cargo xtask codegen` runs the autocxx generation pass once (same code path as
+//! `build.rs`'s `regenerate-bindings` feature), formats it, strips Doxygen doc comments,
+//! and writes the result into `rust/src/generated/bindings.rs` alongside a content-hash
+//! stamp. `cargo xtask tidy` re-derives that hash and fails if the committed bindings are
+//! stale, so CI catches a `rust/src/lib.rs` or header edit that forgot to re-run codegen.
+
+use std::{env, process::ExitCode};
+
+#[path = "../../rust/build/mod.rs"]
+mod build;
+
+fn main() -> ExitCode {
+    let task = env::args().nth(1).unwrap_or_default();
+    match task.as_str() {
+        "codegen" => codegen(),
+        "tidy" => tidy(),
+        _ => {
+            eprintln!("usage: cargo xtask <codegen|tidy>");
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn codegen() -> ExitCode {
+    let ctx = build::common::collect_build_context();
+    build::autocxx::build_autocxx_bridge(&ctx);
+    build::autocxx::format_generated_bindings_optional(&ctx.out_dir);
+    build::autocxx::strip_autocxx_generated_doc_comments(&ctx.out_dir);
+    if let Err(err) = build::autocxx::commit_generated_bindings(&ctx) {
+        eprintln!("failed to write rust/src/generated/bindings.rs: {}", err);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn tidy() -> ExitCode {
+    let ctx = build::common::collect_build_context();
+    if build::autocxx::committed_bindings_are_stale(&ctx) {
+        eprintln!("rust/src/generated/bindings.rs is stale; run `cargo xtask codegen`");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}